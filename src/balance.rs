@@ -12,6 +12,20 @@ pub const LASER_DAMAGE_PER_SECOND: f32 = 100.;
 pub const LASER_RANGE_RADIUS: f32 = 1.5;
 pub const LASER_SHOT_DURATION_SECS: f32 = 1.;
 pub const LASER_RELOAD_DURATION_SECS: f32 = 1.;
+pub const LASER_ARMOR_PIERCING: f32 = 0.;
+
+pub const MICROWAVE_TOWER_ENERGY_COST: f32 = 250.;
+pub const MICROWAVE_TOWER_MATERIALS_COST: f32 = 100.;
+pub const MICROWAVE_RANGE_RADIUS: f32 = 1.25;
+pub const MICROWAVE_DAMAGE_PER_PULSE: f32 = 8.;
+pub const MICROWAVE_SLOW_FACTOR: f32 = 0.5;
+pub const MICROWAVE_SLOW_DURATION_SECS: f32 = 1.5;
+pub const MICROWAVE_PULSE_INTERVAL_SECS: f32 = 0.5;
+pub const MICROWAVE_SHOT_DURATION_SECS: f32 = 2.;
+pub const MICROWAVE_RELOAD_DURATION_SECS: f32 = 1.;
+// Energy is taken in packages of this size while the tower fires
+pub const MICROWAVE_ENERGY_PACKAGE: f32 = 5.;
+pub const MICROWAVE_ENERGY_PER_SECOND: f32 = 5.;
 
 pub const ROCKET_TOWER_ENERGY_COST: f32 = 400.;
 pub const ROCKET_TOWER_MATERIALS_COST: f32 = 350.;
@@ -23,6 +37,36 @@ pub const ROCKET_FUEL_RANGE_TILES: f32 = 5.;
 pub const ROCKET_FUEL_CONSUMPTION_PER_SECOND: f32 = 1.;
 pub const ROCKET_RELOAD_DURATION_SECS: f32 = 4.5;
 pub const ROCKET_SHOOT_DURATION_SECS: f32 = 1.;
+pub const ROCKET_ARMOR_PIERCING: f32 = 0.25;
+
+pub const GRENADE_TOWER_ENERGY_COST: f32 = 200.;
+pub const GRENADE_TOWER_MATERIALS_COST: f32 = 250.;
+pub const GRENADE_DAMAGE: f32 = 150.;
+pub const GRENADE_DAMAGE_RADIUS: f32 = 0.5;
+pub const GRENADE_RANGE_RADIUS: f32 = 2.25;
+// Enemies closer than this are below the lobbing arc
+pub const GRENADE_MIN_RANGE_RADIUS: f32 = 0.75;
+pub const GRENADE_FLIGHT_DURATION_SECS: f32 = 0.9;
+pub const GRENADE_ARC_HEIGHT_TILES: f32 = 0.75;
+pub const GRENADE_MATERIALS_PER_SHOT: f32 = 10.;
+pub const GRENADE_RELOAD_DURATION_SECS: f32 = 2.5;
+pub const GRENADE_SHOOT_DURATION_SECS: f32 = 0.5;
+pub const GRENADE_ARMOR_PIERCING: f32 = 0.1;
+
+// Towers start at level 1, upgrades are bought in the tower panel
+pub const TOWER_MAX_LEVEL: u8 = 3;
+// Share of the build cost per upgrade, multiplied with the current level
+pub const TOWER_UPGRADE_COST_FACTOR: f32 = 0.75;
+pub const TOWER_UPGRADE_RANGE_FACTOR: f32 = 1.1;
+pub const TOWER_UPGRADE_DAMAGE_FACTOR: f32 = 1.35;
+pub const TOWER_UPGRADE_RELOAD_FACTOR: f32 = 0.85;
+pub const TOWER_UPGRADE_SHOT_DURATION_FACTOR: f32 = 1.1;
+
+// Share of the spent resources a sale gives back, it shrinks while the
+// tower or building stands, down to the minimum
+pub const SELL_REFUND_FACTOR: f32 = 0.75;
+pub const SELL_REFUND_MIN_FACTOR: f32 = 0.4;
+pub const SELL_REFUND_DECAY_PER_MINUTE: f32 = 0.05;
 
 pub const POWER_PLANT_ENERGY_COST: f32 = 300.;
 pub const POWER_PLANT_MATERIALS_COST: f32 = 150.;
@@ -36,21 +80,12 @@ pub const FACTORY_ENERGY_PER_SECOND: f32 = 4.;
 pub const FACTORY_MATERIALS_BUFFER_SIZE: f32 = 5.;
 pub const FACTORY_MATERIALS_PER_SECOND: f32 = 2.;
 
-pub const NORMAL_ENEMY_SIZE_RADIUS: f32 = 0.125;
-pub const NORMAL_ENEMY_SPEED_TILES_PER_SECOND: f32 = 1.;
-pub const NORMAL_ENEMY_HEALTH: f32 = 100.;
-
-pub const SPEEDER_ENEMY_SIZE_RADIUS: f32 = 0.075;
-pub const SPEEDER_ENEMY_SPEED_TILES_PER_SECOND: f32 = 2.;
-pub const SPEEDER_ENEMY_HEALTH: f32 = 10.;
-
-pub const TANK_ENEMY_SIZE_RADIUS: f32 = 0.25;
-pub const TANK_ENEMY_SPEED_TILES_PER_SECOND: f32 = 0.5;
-pub const TANK_ENEMY_HEALTH: f32 = 800.;
+pub const MUD_SPEED_FACTOR: f32 = 0.5;
+pub const BOOST_PAD_SPEED_FACTOR: f32 = 1.5;
+pub const HIGH_GROUND_RANGE_FACTOR: f32 = 1.25;
 
+// Enemy kinds are defined in assets/enemies.json
 pub const ENEMY_BREAK_RADIUS_PADDING_FACTOR: f32 = 0.1;
-pub const ENEMY_KILL_MATERIAL_REWARD_DIVISOR: f32 = 5.;
-pub const ENEMY_BASE_DAMAGE_MULTIPLIER: f32 = 5.;
 pub const ENEMY_DEATH_EXPLOSION_RADIUS_FACTOR: f32 = 1.2;
 pub const ENEMY_DEATH_EXPLOSION_DAMAGE_DIVISOR: f32 = 20.;
 
@@ -59,4 +94,5 @@ pub const WAVE_ENEMIES_PER_WAVE: u32 = 2;
 pub const WAVE_START_ENEMY_SPAWN_INTERVAL_SECS: f32 = 0.8;
 pub const WAVE_ENEMY_SPAWN_INTERVAL_DECREASE_PER_WAVE_SECS: f32 = 0.05;
 pub const WAVE_MIN_ENEMY_SPAWN_INTERVAL_SECS: f32 = 0.35;
-pub const TANK_SPAWN_EVERY_NTH_ENEMY: u32 = 10;
+// On maps with several road entrances, every nth wave comes through only one of them
+pub const WAVE_SINGLE_ENTRANCE_EVERY_NTH_WAVE: u32 = 4;
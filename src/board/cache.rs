@@ -1,62 +1,170 @@
-use super::{spawn_line::SpawnLine, step::BoardStep, Board, Tile};
+use super::{
+    spawn_line::SpawnLine,
+    step::{BoardStep, RoadPath},
+    validation::{ValidationError, ValidationErrors},
+    Board, Tile,
+};
 use crate::utils::Vec2Board;
 use bevy::prelude::*;
 use indexmap::IndexSet;
 
 const TILE_NEIGHBOR_MATRIX: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
 
+// More routes are hard to read for the player and slow to search
+const MAX_ROAD_ROUTES: usize = 16;
+
+// Road loops make the number of ways explode, the search gives up after this many steps
+const MAX_ROUTE_SEARCH_STEPS: usize = 50_000;
+
+// Road start at the board edge, enemies spawn here
+#[derive(Clone, Debug)]
+pub struct RoadEntrance {
+    pub pos: UVec2,
+    pub spawn_line: SpawnLine,
+    // One path per route to the road end, forks and merges lead to more routes
+    pub road_paths: Vec<RoadPath>,
+}
+
 #[derive(Resource, Clone)]
 pub struct BoardCache {
     pub tower_tile_posis: IndexSet<UVec2>,
     pub building_tile_posis: IndexSet<UVec2>,
     pub road_tile_posis: IndexSet<UVec2>,
-    pub road_start_pos: Option<UVec2>,
+    pub road_entrances: Vec<RoadEntrance>,
     pub road_end_pos: Option<UVec2>,
-    pub road_path: Vec<BoardStep>,
-    pub spawn_line: SpawnLine,
+}
+
+impl BoardCache {
+    pub fn road_path(&self, entrance: usize, route: usize) -> &RoadPath {
+        &self.road_entrances[entrance].road_paths[route]
+    }
+
+    pub fn all_road_paths(&self) -> impl Iterator<Item = &RoadPath> {
+        self.road_entrances
+            .iter()
+            .flat_map(|entrance| entrance.road_paths.iter())
+    }
+
+    // All routes share the end, so the first route is enough
+    pub fn last_road_step(&self) -> Option<&BoardStep> {
+        self.all_road_paths().next().and_then(|path| path.last())
+    }
 }
 
 // Static functions
 impl BoardCache {
     pub fn new(board: &Board) -> Self {
-        let tower_tile_posis = board.get_tiles(Tile::TowerGround);
+        let tower_tile_posis = board.get_tiles_where(Tile::is_tower_ground);
         let building_tile_posis = board.get_tiles(Tile::BuildingGround);
-        let mut road_tile_posis = board.get_tiles(Tile::Road);
-        let road_start = Self::road_start_pos_from(&road_tile_posis, board.width, board.height);
+        let road_tile_posis = board.get_tiles_where(Tile::is_road);
         let road_end = Self::road_end_pos_from(&road_tile_posis, &building_tile_posis);
-        let mut road_path = Vec::new();
-        if let Some(road_start) = road_start {
-            if let Some(posis) = Self::are_tiles_connected(&road_tile_posis, road_start) {
-                road_tile_posis = posis;
-                road_path = Self::calc_road_path(&road_tile_posis);
-            }
-        }
         Self {
-            spawn_line: SpawnLine::new(road_start, road_path.first()),
+            road_entrances: Self::road_entrances_from(&road_tile_posis, board, road_end),
             tower_tile_posis,
             building_tile_posis,
             road_tile_posis,
-            road_start_pos: road_start,
             road_end_pos: road_end,
-            road_path,
         }
     }
 
-    // is tile at one edge of the board and has only one road neighbor, it is the starting point
-    fn road_start_pos_from(
+    fn road_entrances_from(
         road_tile_posis: &IndexSet<UVec2>,
-        board_width: u8,
-        board_heigt: u8,
-    ) -> Option<UVec2> {
-        for pos in road_tile_posis {
-            let (x, y) = (pos.x, pos.y);
-            if (x == 0 || y == 0 || x == board_width as u32 - 1 || y == board_heigt as u32 - 1)
-                && Self::get_neighbors(pos, road_tile_posis).len() == 1
-            {
-                return Some(*pos);
+        board: &Board,
+        end: Option<UVec2>,
+    ) -> Vec<RoadEntrance> {
+        Self::road_start_posis_from(road_tile_posis, board.width, board.height)
+            .into_iter()
+            .filter(|start| Some(*start) != end)
+            .map(|start| {
+                let road_paths = Self::calc_road_paths(road_tile_posis, start, end);
+                RoadEntrance {
+                    pos: start,
+                    spawn_line: SpawnLine::new(
+                        Some(start),
+                        road_paths.first().and_then(|p| p.first()),
+                    ),
+                    road_paths,
+                }
+            })
+            .collect()
+    }
+
+    fn calc_road_paths(
+        road_tile_posis: &IndexSet<UVec2>,
+        start: UVec2,
+        end: Option<UVec2>,
+    ) -> Vec<RoadPath> {
+        match end {
+            // A wide road has too many ways through it to search them all
+            Some(_) if !Self::tile_squares(road_tile_posis).is_empty() => Vec::new(),
+            Some(end) => Self::road_routes(road_tile_posis, start, end)
+                .unwrap_or_default()
+                .iter()
+                .map(Self::calc_road_path)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    // Every way from start to end without visiting a tile twice, stops one above the maximum.
+    // None if the search ran out of steps, which only road loops cause.
+    fn road_routes(
+        road_tile_posis: &IndexSet<UVec2>,
+        start: UVec2,
+        end: UVec2,
+    ) -> Option<Vec<IndexSet<UVec2>>> {
+        let mut routes = Vec::new();
+        let mut route = IndexSet::from([start]);
+        let mut steps_left = MAX_ROUTE_SEARCH_STEPS;
+        Self::find_routes(
+            road_tile_posis,
+            end,
+            &mut route,
+            &mut routes,
+            &mut steps_left,
+        );
+        (steps_left > 0).then_some(routes)
+    }
+
+    fn find_routes(
+        tiles: &IndexSet<UVec2>,
+        end: UVec2,
+        route: &mut IndexSet<UVec2>,
+        routes: &mut Vec<IndexSet<UVec2>>,
+        steps_left: &mut usize,
+    ) {
+        let pos = *route.last().unwrap();
+        if pos == end {
+            routes.push(route.clone());
+            return;
+        }
+        for neighbor in Self::get_neighbors(&pos, tiles) {
+            if routes.len() > MAX_ROAD_ROUTES || *steps_left == 0 {
+                return;
+            }
+            if route.insert(neighbor) {
+                *steps_left -= 1;
+                Self::find_routes(tiles, end, route, routes, steps_left);
+                route.pop();
             }
         }
-        None
+    }
+
+    // is tile at one edge of the board and has only one road neighbor, it is a starting point
+    fn road_start_posis_from(
+        road_tile_posis: &IndexSet<UVec2>,
+        board_width: u8,
+        board_heigt: u8,
+    ) -> Vec<UVec2> {
+        road_tile_posis
+            .iter()
+            .filter(|pos| {
+                let (x, y) = (pos.x, pos.y);
+                (x == 0 || y == 0 || x == board_width as u32 - 1 || y == board_heigt as u32 - 1)
+                    && Self::get_neighbors(pos, road_tile_posis).len() == 1
+            })
+            .copied()
+            .collect()
     }
 
     fn get_neighbors(pos: &UVec2, tiles: &IndexSet<UVec2>) -> Vec<UVec2> {
@@ -74,7 +182,7 @@ impl BoardCache {
         neighbors
     }
 
-    fn calc_road_path(rt_posis: &IndexSet<UVec2>) -> Vec<BoardStep> {
+    fn calc_road_path(rt_posis: &IndexSet<UVec2>) -> RoadPath {
         let pos = Vec2Board::from_uvec2_middle(rt_posis.get_index(0).unwrap());
         let mut path = vec![BoardStep::new(
             0,
@@ -121,45 +229,84 @@ impl BoardCache {
 
 // Validation
 impl BoardCache {
-    pub fn validate(&self) -> Result<(), &str> {
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        use ValidationError::*;
+        let mut errors = Vec::new();
         if self.tower_tile_posis.is_empty() {
-            return Err("Need tower tiles");
+            errors.push(NoTowerTiles);
         }
         if self.building_tile_posis.len() < 5 {
-            return Err("Need minimal five building tiles");
+            errors.push(TooFewBuildingTiles(Self::to_vec(&self.building_tile_posis)));
+        }
+        if let Some(build_tile) = self.building_tile_posis.first() {
+            let tiles = Self::disconnected_tiles(&self.building_tile_posis, *build_tile);
+            if !tiles.is_empty() {
+                errors.push(DisconnectedBuildings(tiles));
+            }
         }
         if self.road_tile_posis.len() < 3 {
-            return Err("Need minimal two road tiles");
+            errors.push(TooFewRoadTiles(Self::to_vec(&self.road_tile_posis)));
         }
-        if let Some(start) = self.road_start_pos {
-            if Self::are_tiles_connected(&self.road_tile_posis, start).is_none() {
-                return Err("All road tiles must be connected to each other");
+        match self.road_entrances.first() {
+            Some(start) => {
+                let tiles = Self::disconnected_tiles(&self.road_tile_posis, start.pos);
+                if !tiles.is_empty() {
+                    errors.push(DisconnectedRoad(tiles));
+                }
             }
-        } else {
-            return Err("Need road starting point at the board edge");
+            None => errors.push(NoRoadStart),
         }
-        let build_tile = self.building_tile_posis.first().unwrap();
-        if Self::are_tiles_connected(&self.building_tile_posis, *build_tile).is_none() {
-            return Err("All building tiles must be connected to each other");
+        let square_tiles = Self::tile_squares(&self.road_tile_posis);
+        let is_wide_road = !square_tiles.is_empty();
+        if is_wide_road {
+            errors.push(WideRoad(square_tiles));
         }
-        if Self::have_tiles_more_than_max_neighbors(2, &self.road_tile_posis) {
-            return Err("Only one clear road allowed");
+        match self.road_end_pos {
+            // Routes through wide roads are neither calculated nor checked
+            Some(end) if !is_wide_road => errors.extend(self.validate_routes(end)),
+            Some(_) => (),
+            None => errors.push(NoRoadEnd),
         }
-        if self.road_end_pos.is_none() {
-            return Err("Road must end surrounded by three building tiles");
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(ValidationErrors(errors)),
         }
+    }
 
-        Ok(())
+    fn validate_routes(&self, end: UVec2) -> Option<ValidationError> {
+        let Some(routes) = self
+            .road_entrances
+            .iter()
+            .map(|entrance| Self::road_routes(&self.road_tile_posis, entrance.pos, end))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Some(ValidationError::TooManyRoutes(MAX_ROAD_ROUTES));
+        };
+        let routes: Vec<IndexSet<UVec2>> = routes.into_iter().flatten().collect();
+        if routes.len() > MAX_ROAD_ROUTES {
+            return Some(ValidationError::TooManyRoutes(MAX_ROAD_ROUTES));
+        }
+        let dead_ends: Vec<UVec2> = self
+            .road_tile_posis
+            .iter()
+            .filter(|pos| !routes.iter().any(|route| route.contains(*pos)))
+            .copied()
+            .collect();
+        match dead_ends.is_empty() {
+            true => None,
+            false => Some(ValidationError::DeadEndRoad(dead_ends)),
+        }
+    }
+
+    fn to_vec(tiles: &IndexSet<UVec2>) -> Vec<UVec2> {
+        tiles.iter().copied().collect()
     }
 
-    fn are_tiles_connected(tile_posis: &IndexSet<UVec2>, start: UVec2) -> Option<IndexSet<UVec2>> {
+    fn disconnected_tiles(tile_posis: &IndexSet<UVec2>, start: UVec2) -> Vec<UVec2> {
         let mut connected_tiles: IndexSet<UVec2> = IndexSet::new();
         Self::check_neighbors(start, tile_posis, &mut connected_tiles);
-        if tile_posis.len() == connected_tiles.len() {
-            Some(connected_tiles)
-        } else {
-            None
-        }
+        tile_posis.difference(&connected_tiles).copied().collect()
     }
 
     fn check_neighbors(pos: UVec2, tiles: &IndexSet<UVec2>, linked: &mut IndexSet<UVec2>) {
@@ -171,13 +318,17 @@ impl BoardCache {
         }
     }
 
-    fn have_tiles_more_than_max_neighbors(max: usize, tiles: &IndexSet<UVec2>) -> bool {
-        for tile in tiles {
-            if Self::get_neighbors(tile, tiles).len() > max {
-                return true;
+    // Two by two tiles would make the road wider than one tile
+    fn tile_squares(tiles: &IndexSet<UVec2>) -> Vec<UVec2> {
+        let mut square_tiles = IndexSet::new();
+        for pos in tiles {
+            let square =
+                [(0, 0), (1, 0), (0, 1), (1, 1)].map(|(x, y)| UVec2::new(pos.x + x, pos.y + y));
+            if square.iter().all(|pos| tiles.contains(pos)) {
+                square_tiles.extend(square);
             }
         }
-        false
+        square_tiles.into_iter().collect()
     }
 }
 
@@ -185,18 +336,18 @@ impl BoardCache {
 impl BoardCache {
     pub fn get_tile_posis_mut(&mut self, tile: &Tile) -> Option<&mut IndexSet<UVec2>> {
         match tile {
-            Tile::TowerGround => Some(&mut self.tower_tile_posis),
+            tile if tile.is_tower_ground() => Some(&mut self.tower_tile_posis),
+            tile if tile.is_road() => Some(&mut self.road_tile_posis),
             Tile::BuildingGround => Some(&mut self.building_tile_posis),
-            Tile::Road => Some(&mut self.road_tile_posis),
-            Tile::Empty => None,
+            _ => None,
         }
     }
 
     pub fn calc_road_data(&mut self, board: &Board) {
-        self.road_start_pos =
-            Self::road_start_pos_from(&self.road_tile_posis, board.width, board.height);
         self.road_end_pos =
             Self::road_end_pos_from(&self.road_tile_posis, &self.building_tile_posis);
+        self.road_entrances =
+            Self::road_entrances_from(&self.road_tile_posis, board, self.road_end_pos);
     }
 
     pub fn remove_tile_pos(&mut self, pos: &UVec2, tile: &Tile) {
@@ -224,3 +375,129 @@ impl BoardCache {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // T: tower ground, B: building ground, R: road, M: mud, rows from y = 0 upwards
+    fn board(rows: &[&str]) -> Board {
+        let tiles: Vec<Vec<Tile>> = rows
+            .iter()
+            .map(|row| {
+                row.chars()
+                    .map(|c| match c {
+                        'T' => Tile::TowerGround,
+                        'B' => Tile::BuildingGround,
+                        'R' => Tile::Road,
+                        'M' => Tile::Mud,
+                        _ => Tile::Empty,
+                    })
+                    .collect()
+            })
+            .collect();
+        Board {
+            name: String::from("test"),
+            width: tiles[0].len() as u8,
+            height: tiles.len() as u8,
+            tiles,
+        }
+    }
+
+    #[test]
+    fn test_fork_and_merge_has_two_routes() {
+        let cache = BoardCache::new(&board(&[
+            "TTTTTTT", "TTRRRBB", "RRRTRRB", "TTRRRBB", "TTTTTBB",
+        ]));
+        assert_eq!(cache.validate(), Ok(()));
+        assert_eq!(cache.road_entrances.len(), 1);
+        assert_eq!(cache.all_road_paths().count(), 2);
+        for path in cache.all_road_paths() {
+            let last = path.last().unwrap();
+            assert_eq!(last.start_pos, Vec2Board::new(4.5, 2.5));
+            assert_eq!(last.direction, cache.last_road_step().unwrap().direction);
+        }
+    }
+
+    #[test]
+    fn test_two_entrances_have_own_spawn_lines() {
+        let cache = BoardCache::new(&board(&[
+            "TTRTTTT", "TTRTTBB", "RRRRRRB", "TTTTTBB", "TTTTTBB",
+        ]));
+        assert_eq!(cache.validate(), Ok(()));
+        let entrances: Vec<UVec2> = cache.road_entrances.iter().map(|e| e.pos).collect();
+        assert_eq!(entrances, vec![UVec2::new(2, 0), UVec2::new(0, 2)]);
+        for entrance in cache.road_entrances.iter() {
+            assert_eq!(entrance.road_paths.len(), 1);
+            assert_eq!(entrance.spawn_line.start, Vec2Board::from(entrance.pos));
+        }
+    }
+
+    #[test]
+    fn test_mud_is_part_of_the_road() {
+        let cache = BoardCache::new(&board(&[
+            "TTTTTTT", "TTTTTBB", "RRMMRRB", "TTTTTBB", "TTTTTBB",
+        ]));
+        assert_eq!(cache.validate(), Ok(()));
+        assert_eq!(cache.road_tile_posis.len(), 6);
+        assert_eq!(cache.road_end_pos, Some(UVec2::new(5, 2)));
+    }
+
+    #[test]
+    fn test_dead_end_branch_is_invalid() {
+        let cache = BoardCache::new(&board(&[
+            "TTTTTTT", "TTTRTTT", "TTRRRBB", "RRRTRRB", "TTRRRBB", "TTTTTBB",
+        ]));
+        assert_eq!(
+            cache.validate(),
+            Err(ValidationErrors(vec![ValidationError::DeadEndRoad(vec![
+                UVec2::new(3, 1)
+            ])]))
+        );
+    }
+
+    #[test]
+    fn test_all_errors_are_found_at_once() {
+        let cache = BoardCache::new(&board(&["RRTT", "TTTT", "TTRT", "TTTB"]));
+        let errors = cache.validate().unwrap_err();
+        assert!(
+            errors.contains(&ValidationError::TooFewBuildingTiles(vec![UVec2::new(
+                3, 3
+            )]))
+        );
+        assert!(errors.contains(&ValidationError::DisconnectedRoad(vec![UVec2::new(2, 2)])));
+        assert!(errors.contains(&ValidationError::NoRoadEnd));
+    }
+
+    #[test]
+    fn test_route_search_on_road_grid_gives_up() {
+        // One tile wide roads in a grid, every crossing doubles the ways
+        let tiles: IndexSet<UVec2> = (0..41)
+            .flat_map(|y| (0..41).map(move |x| UVec2::new(x, y)))
+            .filter(|pos| pos.x % 2 == 0 || pos.y % 2 == 0)
+            .collect();
+        let unreachable = UVec2::new(100, 100);
+        assert_eq!(
+            BoardCache::road_routes(&tiles, UVec2::ZERO, unreachable),
+            None
+        );
+    }
+
+    #[test]
+    fn test_wide_road_is_invalid() {
+        let cache = BoardCache::new(&board(&[
+            "TTTTTTT", "TRRRRBB", "RRRTRRB", "TTRRRBB", "TTTTTBB",
+        ]));
+        let errors = cache.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].tiles(),
+            &[
+                UVec2::new(1, 1),
+                UVec2::new(2, 1),
+                UVec2::new(1, 2),
+                UVec2::new(2, 2)
+            ]
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use super::{Board, BoardCache, Tile};
+use crate::utils::{GameRng, Seed};
+use bevy::prelude::*;
+use indexmap::IndexSet;
+use rand::RngExt;
+use std::error::Error;
+
+// Attempts before the parameters are considered impossible
+const MAX_ATTEMPTS: usize = 500;
+
+pub const MIN_GENERATED_BOARD_SIZE: u8 = 7;
+
+const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorParams {
+    pub width: u8,
+    pub height: u8,
+    // Minimal number of road tiles from the board edge to the road end
+    pub road_length: usize,
+    pub bends: u32,
+    pub building_size: u32,
+    // Share of the tiles near the road which become tower ground
+    pub tower_density: f32,
+}
+
+impl Default for GeneratorParams {
+    fn default() -> Self {
+        Self {
+            width: 16,
+            height: 10,
+            road_length: 16,
+            bends: 4,
+            building_size: 12,
+            tower_density: 0.6,
+        }
+    }
+}
+
+// Same parameters and seed always build the same board
+pub fn generate_board(params: &GeneratorParams, seed: Seed) -> Result<Board, Box<dyn Error>> {
+    if params.width < MIN_GENERATED_BOARD_SIZE || params.height < MIN_GENERATED_BOARD_SIZE {
+        return Err(format!(
+            "Generated maps need at least {MIN_GENERATED_BOARD_SIZE}x{MIN_GENERATED_BOARD_SIZE} tiles"
+        )
+        .into());
+    }
+    let mut rng = GameRng::new(seed);
+    for _ in 0..MAX_ATTEMPTS {
+        let Some(mut board) = try_generate(params, &mut rng) else {
+            continue;
+        };
+        if BoardCache::new(&board).validate().is_ok() {
+            board.name = format!("Random {seed}");
+            return Ok(board);
+        }
+    }
+    Err("No valid map found for these parameters, try a shorter road or fewer bends".into())
+}
+
+fn try_generate(params: &GeneratorParams, rng: &mut GameRng) -> Option<Board> {
+    let mut board = Board::empty(params.width, params.height);
+    let (end, exit_dir) = place_buildings(&mut board, params, rng)?;
+    let road = walk_road(&board, end, exit_dir, params, rng)?;
+    for pos in road.iter() {
+        set_tile(&mut board, *pos, Tile::Road);
+    }
+    place_tower_ground(&mut board, &road, params.tower_density, rng);
+    Some(board)
+}
+
+fn set_tile(board: &mut Board, pos: UVec2, tile: Tile) {
+    if let Some(board_tile) = board.get_tile_mut(&pos) {
+        *board_tile = tile;
+    }
+}
+
+fn is_inside(board: &Board, pos: IVec2) -> bool {
+    pos.x >= 0 && pos.y >= 0 && pos.x < board.width as i32 && pos.y < board.height as i32
+}
+
+fn is_edge(board: &Board, pos: IVec2) -> bool {
+    pos.x == 0 || pos.y == 0 || pos.x == board.width as i32 - 1 || pos.y == board.height as i32 - 1
+}
+
+// Building rectangle with a notch for the road end, so the end has three building neighbors
+fn place_buildings(
+    board: &mut Board,
+    params: &GeneratorParams,
+    rng: &mut GameRng,
+) -> Option<(IVec2, IVec2)> {
+    let max_size = IVec2::new(board.width as i32 - 2, board.height as i32 - 2);
+    let width = rng.random_range(3..=((params.building_size as f32).sqrt().ceil() as i32).max(3));
+    let height = (params.building_size as i32 / width).max(3);
+    let size = IVec2::new(width, height).min(max_size);
+    let min = IVec2::new(
+        rng.random_range(1..=max_size.x - size.x + 1),
+        rng.random_range(1..=max_size.y - size.y + 1),
+    );
+    let max = min + size - IVec2::ONE;
+
+    let exit_dir = DIRECTIONS[rng.random_range(0..DIRECTIONS.len())];
+    let end = match exit_dir {
+        IVec2::X => IVec2::new(max.x, rng.random_range(min.y + 1..max.y)),
+        IVec2::NEG_X => IVec2::new(min.x, rng.random_range(min.y + 1..max.y)),
+        IVec2::Y => IVec2::new(rng.random_range(min.x + 1..max.x), max.y),
+        _ => IVec2::new(rng.random_range(min.x + 1..max.x), min.y),
+    };
+    if is_edge(board, end + exit_dir) {
+        return None;
+    }
+    for y in min.y..=max.y {
+        for x in min.x..=max.x {
+            set_tile(board, UVec2::new(x as u32, y as u32), Tile::BuildingGround);
+        }
+    }
+    Some((end, exit_dir))
+}
+
+// Random walk from the road end to the board edge, which never touches itself,
+// so the road stays one tile wide without branches
+fn walk_road(
+    board: &Board,
+    end: IVec2,
+    exit_dir: IVec2,
+    params: &GeneratorParams,
+    rng: &mut GameRng,
+) -> Option<Vec<UVec2>> {
+    let mut road: IndexSet<IVec2> = IndexSet::from([end, end + exit_dir]);
+    let mut dir = exit_dir;
+    let mut bends_left = params.bends;
+    let max_steps = board.width as usize * board.height as usize;
+    while road.len() < max_steps {
+        let pos = *road.last()?;
+        if road.len() >= params.road_length.max(3) && is_edge(board, pos) {
+            return Some(road.into_iter().map(|pos| pos.as_uvec2()).collect());
+        }
+
+        // Bends get spread over the expected road length
+        let steps_left = params.road_length.saturating_sub(road.len()).max(1);
+        let bend_chance = (bends_left as f64 / steps_left as f64).clamp(0.05, 0.9);
+        let turns = match rng.random_bool(0.5) {
+            true => [dir.perp(), -dir.perp()],
+            false => [-dir.perp(), dir.perp()],
+        };
+        let options = match bends_left > 0 && rng.random_bool(bend_chance) {
+            true => [turns[0], turns[1], dir],
+            false => [dir, turns[0], turns[1]],
+        };
+        let next_dir = options.into_iter().find(|next_dir| {
+            let next = pos + *next_dir;
+            is_inside(board, next)
+                && board.get_tile(&next.as_uvec2()) == Some(&Tile::Empty)
+                && !road.contains(&next)
+                && (road.len() + 1 >= params.road_length || !is_edge(board, next))
+                && DIRECTIONS
+                    .iter()
+                    .all(|side| next + *side == pos || !road.contains(&(next + *side)))
+        })?;
+        if next_dir != dir {
+            bends_left = bends_left.saturating_sub(1);
+            dir = next_dir;
+        }
+        road.insert(pos + dir);
+    }
+    None
+}
+
+fn place_tower_ground(board: &mut Board, road: &[UVec2], density: f32, rng: &mut GameRng) {
+    let mut tower_count = 0;
+    for y in 0..board.height as i32 {
+        for x in 0..board.width as i32 {
+            let pos = IVec2::new(x, y);
+            let is_near_road = road
+                .iter()
+                .any(|road_pos| (road_pos.as_ivec2() - pos).abs().max_element() <= 1);
+            if board.get_tile(&pos.as_uvec2()) == Some(&Tile::Empty)
+                && is_near_road
+                && (tower_count == 0 || rng.random_bool(density.clamp(0., 1.) as f64))
+            {
+                set_tile(board, pos.as_uvec2(), Tile::TowerGround);
+                tower_count += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_boards_are_valid() {
+        let params = GeneratorParams::default();
+        for seed in 0..50 {
+            let board = generate_board(&params, seed).unwrap();
+            assert_eq!(BoardCache::new(&board).validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_board() {
+        let params = GeneratorParams {
+            width: 20,
+            height: 12,
+            bends: 8,
+            ..Default::default()
+        };
+        let board = generate_board(&params, 7).unwrap();
+        assert_eq!(board.tiles, generate_board(&params, 7).unwrap().tiles);
+    }
+}
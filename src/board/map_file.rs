@@ -0,0 +1,440 @@
+use super::{Board, Tile};
+use crate::utils::file_name_of;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    error::Error,
+    fs::{read_dir, read_to_string, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+pub const MAPS_DIR: &str = "./maps/";
+pub const RESAVE_MAPS_ARG: &str = "--resave-maps";
+
+// Increase on every change of the map format and add a migration step for the old one
+pub const MAP_FORMAT_VERSION: u32 = 1;
+
+// Both formats hold the same data, ascii is the one to read in diffs and chats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MapFormat {
+    #[default]
+    Json,
+    Ascii,
+}
+
+impl MapFormat {
+    fn file_suffix(&self) -> &'static str {
+        match self {
+            MapFormat::Json => "_map.json",
+            MapFormat::Ascii => "_map.txt",
+        }
+    }
+
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(MapFormat::Json),
+            "txt" => Some(MapFormat::Ascii),
+            _ => None,
+        }
+    }
+
+    // Only files named like the ones save_board_to_file writes, notes next to the maps are skipped
+    fn from_map_file_name(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        [MapFormat::Json, MapFormat::Ascii]
+            .into_iter()
+            .find(|format| file_name.ends_with(format.file_suffix()))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapMeta {
+    pub author: String,
+    pub description: String,
+    // Game version which wrote the file
+    pub game_version: String,
+}
+
+// What a map file contains, the board plus everything needed to read it in the future
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MapFile {
+    version: u32,
+    pub meta: MapMeta,
+    pub board: Board,
+}
+
+impl MapFile {
+    pub fn new(board: Board, meta: MapMeta) -> Self {
+        Self {
+            version: MAP_FORMAT_VERSION,
+            meta: MapMeta {
+                game_version: env!("CARGO_PKG_VERSION").to_string(),
+                ..meta
+            },
+            board,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        let mut value: Value = serde_json::from_str(json)?;
+        loop {
+            match map_format_version(&value) {
+                0 => value = migrate_v0(value),
+                MAP_FORMAT_VERSION => return Ok(serde_json::from_value(value)?),
+                version => {
+                    return Err(format!(
+                        "Map format version {version} is newer than the supported version {MAP_FORMAT_VERSION}"
+                    )
+                    .into())
+                }
+            }
+        }
+    }
+
+    // Header lines "key: value", then the tile rows from the top row down
+    pub fn from_ascii(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut meta = MapMeta::default();
+        let mut name = String::new();
+        let mut size = None;
+        let mut rows = Vec::new();
+        for line in text.lines().map(str::trim_end) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once(':') {
+                let value = value.trim().to_string();
+                match key.trim() {
+                    "name" => name = value,
+                    "size" => size = Some(parse_ascii_size(&value)?),
+                    "author" => meta.author = value,
+                    "description" => meta.description = value,
+                    "game_version" => meta.game_version = value,
+                    key => return Err(format!("Unknown map header '{key}'").into()),
+                }
+                continue;
+            }
+            let row = line
+                .chars()
+                .map(|c| {
+                    ascii_tile(c).ok_or(format!("Unknown tile '{c}' in row {}", rows.len() + 1))
+                })
+                .collect::<Result<Vec<Tile>, String>>()?;
+            rows.push(row);
+        }
+
+        let (width, height) = size.ok_or("Map header 'size' is missing")?;
+        if rows.len() != height as usize || rows.iter().any(|row| row.len() != width as usize) {
+            return Err(format!("Tile rows don't match the size {width}x{height}").into());
+        }
+        rows.reverse();
+        let board = Board {
+            name,
+            width,
+            height,
+            tiles: rows,
+        };
+        Ok(Self {
+            version: MAP_FORMAT_VERSION,
+            meta,
+            board,
+        })
+    }
+
+    pub fn to_ascii(&self) -> String {
+        let board = &self.board;
+        let mut text = format!(
+            "name: {}\nsize: {}x{}\n",
+            board.name, board.width, board.height
+        );
+        for (key, value) in [
+            ("author", &self.meta.author),
+            ("description", &self.meta.description),
+            ("game_version", &self.meta.game_version),
+        ] {
+            if !value.is_empty() {
+                text += &format!("{key}: {}\n", value.replace('\n', " "));
+            }
+        }
+        text.push('\n');
+        for row in board.tiles.iter().rev() {
+            text.extend(row.iter().map(|tile| tile_ascii(*tile)));
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = read_to_string(path)?;
+        match MapFormat::from_path(path) {
+            Some(MapFormat::Ascii) => Self::from_ascii(&text),
+            _ => Self::from_json(&text),
+        }
+        .map_err(|err| format!("Invalid map file '{}': {err}", path.display()).into())
+    }
+
+    pub fn to_file(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut output = File::create(path)?;
+        match MapFormat::from_path(path) {
+            Some(MapFormat::Ascii) => write!(output, "{}", self.to_ascii())?,
+            _ => write!(output, "{}", serde_json::to_string(self)?)?,
+        }
+        Ok(())
+    }
+}
+
+fn parse_ascii_size(value: &str) -> Result<(u8, u8), Box<dyn Error>> {
+    let (width, height) = value
+        .split_once('x')
+        .ok_or(format!("Map size must look like 16x10, not '{value}'"))?;
+    Ok((width.trim().parse()?, height.trim().parse()?))
+}
+
+fn tile_ascii(tile: Tile) -> char {
+    match tile {
+        Tile::TowerGround => 'T',
+        Tile::BuildingGround => 'B',
+        Tile::Road => 'R',
+        Tile::Empty => '.',
+        Tile::Mud => 'M',
+        Tile::BoostPad => 'P',
+        Tile::HighGround => 'H',
+        Tile::Water => '~',
+        // '#' starts comment lines
+        Tile::Rock => '^',
+    }
+}
+
+fn ascii_tile(c: char) -> Option<Tile> {
+    match c {
+        'T' => Some(Tile::TowerGround),
+        'B' => Some(Tile::BuildingGround),
+        'R' => Some(Tile::Road),
+        '.' => Some(Tile::Empty),
+        'M' => Some(Tile::Mud),
+        'P' => Some(Tile::BoostPad),
+        'H' => Some(Tile::HighGround),
+        '~' => Some(Tile::Water),
+        '^' => Some(Tile::Rock),
+        _ => None,
+    }
+}
+
+// Files without version are bare boards from before the envelope
+fn map_format_version(value: &Value) -> u32 {
+    value
+        .get("version")
+        .and_then(Value::as_u64)
+        .map(|version| version as u32)
+        .unwrap_or(0)
+}
+
+// Bare board, tiles are either "Road" or {"TowerGround":null} from the time tiles had data
+fn migrate_v0(mut board: Value) -> Value {
+    if let Some(rows) = board.get_mut("tiles").and_then(Value::as_array_mut) {
+        for tile in rows
+            .iter_mut()
+            .filter_map(Value::as_array_mut)
+            .flat_map(|row| row.iter_mut())
+        {
+            if let Some(name) = tile.as_object().and_then(|obj| obj.keys().next()).cloned() {
+                *tile = Value::String(name);
+            }
+        }
+    }
+    serde_json::json!({
+        "version": 1,
+        "meta": MapMeta::default(),
+        "board": board,
+    })
+}
+
+pub fn map_path(name: &str, format: MapFormat) -> PathBuf {
+    Path::new(MAPS_DIR).join(format!("{name}{}", format.file_suffix()))
+}
+
+pub fn save_board_to_file(
+    name: &str,
+    board: &Board,
+    meta: &MapMeta,
+    format: MapFormat,
+) -> Result<(), Box<dyn Error>> {
+    // The name is the file name, it must not point out of the maps folder
+    if name.trim().is_empty() || file_name_of(name) != name {
+        return Err(format!(
+            "Invalid map name '{name}', use only letters, digits, spaces, '-' and '_'"
+        )
+        .into());
+    }
+    MapFile::new(board.clone(), meta.clone()).to_file(&map_path(name, format))
+}
+
+// Ascii form of a map, for the clipboard
+pub fn board_to_ascii(board: &Board, meta: &MapMeta) -> String {
+    MapFile::new(board.clone(), meta.clone()).to_ascii()
+}
+
+// Loaded maps of the maps folder, a broken file doesn't hide the other maps
+#[derive(Default)]
+pub struct MapFolder {
+    pub boards: Vec<Board>,
+    pub errors: Vec<String>,
+}
+
+impl MapFolder {
+    pub fn err_text(&self) -> Option<String> {
+        match self.errors.is_empty() {
+            true => None,
+            false => Some(self.errors.join("\n")),
+        }
+    }
+}
+
+pub fn get_all_boards_in_folder() -> Result<MapFolder, Box<dyn Error>> {
+    let (map_files, errors) = get_all_map_files_in_folder()?;
+    Ok(MapFolder {
+        boards: map_files
+            .into_iter()
+            .map(|map_file| map_file.board)
+            .collect(),
+        errors,
+    })
+}
+
+// Like get_all_boards_in_folder, but keeps the meta data of each map
+pub fn get_all_map_files_in_folder() -> Result<(Vec<MapFile>, Vec<String>), Box<dyn Error>> {
+    let mut map_files = Vec::new();
+    let mut errors = Vec::new();
+    for (_, map_file) in map_files_in_folder()? {
+        match map_file {
+            Ok(map_file) => map_files.push(map_file),
+            Err(err) => errors.push(err),
+        }
+    }
+    Ok((map_files, errors))
+}
+
+type MapFileResult = Result<MapFile, String>;
+
+fn map_files_in_folder() -> Result<Vec<(PathBuf, MapFileResult)>, Box<dyn Error>> {
+    let mut paths = read_dir(MAPS_DIR)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .filter(|path| {
+            path.as_ref()
+                .map_or(true, |path| MapFormat::from_map_file_name(path).is_some())
+        })
+        .collect::<Result<Vec<PathBuf>, _>>()?;
+    paths.sort();
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let map_file = MapFile::from_file(&path).map_err(|err| err.to_string());
+            (path, map_file)
+        })
+        .collect())
+}
+
+// Writes every loadable map in the current format
+pub fn resave_all_maps() -> Result<MapFolder, Box<dyn Error>> {
+    let mut folder = MapFolder::default();
+    for (path, map_file) in map_files_in_folder()? {
+        match map_file.and_then(|map_file| {
+            let map_file = MapFile::new(map_file.board, map_file.meta);
+            map_file.to_file(&path).map_err(|err| err.to_string())?;
+            Ok(map_file)
+        }) {
+            Ok(map_file) => folder.boards.push(map_file.board),
+            Err(err) => folder.errors.push(err),
+        }
+    }
+    Ok(folder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Tile;
+
+    #[test]
+    fn test_reject_map_names_outside_maps_dir() {
+        let (board, meta) = (Board::default(), MapMeta::default());
+        for name in ["", "../Snake", "a/b", "Snake.json"] {
+            assert!(save_board_to_file(name, &board, &meta, MapFormat::Json).is_err());
+        }
+    }
+
+    #[test]
+    fn test_migrate_bare_board_with_mixed_tiles() {
+        let json = r#"{"name":"Old","width":2,"height":1,"tiles":[[{"TowerGround":null},"Road"]]}"#;
+        let map_file = MapFile::from_json(json).unwrap();
+        assert_eq!(map_file.version, MAP_FORMAT_VERSION);
+        assert_eq!(map_file.board.name, "Old");
+        assert_eq!(
+            map_file.board.tiles,
+            vec![vec![Tile::TowerGround, Tile::Road]]
+        );
+    }
+
+    #[test]
+    fn test_current_format_round_trip() {
+        let meta = MapMeta {
+            author: String::from("Serveny"),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&MapFile::new(Board::default(), meta)).unwrap();
+        let map_file = MapFile::from_json(&json).unwrap();
+        assert_eq!(map_file.meta.author, "Serveny");
+        assert_eq!(map_file.board.width, Board::default().width);
+    }
+
+    #[test]
+    fn test_ascii_round_trip() {
+        let text = "name: Tiny\nsize: 3x2\nauthor: Serveny\n\nTRB\n.RR\n";
+        let map_file = MapFile::from_ascii(text).unwrap();
+        assert_eq!(map_file.board.name, "Tiny");
+        assert_eq!(map_file.meta.author, "Serveny");
+        assert_eq!(
+            map_file.board.tiles[0],
+            vec![Tile::Empty, Tile::Road, Tile::Road]
+        );
+        assert_eq!(map_file.to_ascii(), text);
+        assert!(MapFile::from_ascii("name: Tiny\nsize: 3x3\n\nTRB\n").is_err());
+    }
+
+    #[test]
+    fn test_board_to_ascii_keeps_meta() {
+        let meta = MapMeta {
+            author: String::from("Serveny"),
+            description: String::from("Two roads"),
+            ..Default::default()
+        };
+        let ascii = board_to_ascii(&Board::default(), &meta);
+        let map_file = MapFile::from_ascii(&ascii).unwrap();
+        assert_eq!(map_file.meta.author, meta.author);
+        assert_eq!(map_file.meta.description, meta.description);
+    }
+
+    #[test]
+    fn test_ascii_row_starting_with_rock() {
+        let text = "name: Rocky\nsize: 3x2\n\n^RB\n~RR\n";
+        let map_file = MapFile::from_ascii(text).unwrap();
+        assert_eq!(map_file.board.tiles[1][0], Tile::Rock);
+        assert_eq!(map_file.to_ascii(), text);
+    }
+
+    #[test]
+    fn test_only_map_files_are_listed() {
+        let format = |name: &str| MapFormat::from_map_file_name(Path::new(name));
+        assert_eq!(format("maps/Snake_map.json"), Some(MapFormat::Json));
+        assert_eq!(format("maps/Snake_map.txt"), Some(MapFormat::Ascii));
+        assert_eq!(format("maps/README.txt"), None);
+        assert_eq!(format("maps/settings.json"), None);
+    }
+
+    #[test]
+    fn test_newer_format_is_rejected() {
+        let json = r#"{"version":999,"meta":{},"board":{}}"#;
+        assert!(MapFile::from_json(json).is_err());
+    }
+}
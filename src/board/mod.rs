@@ -1,5 +1,3 @@
-use std::cmp::Ordering;
-
 pub use self::cache::BoardCache;
 pub use self::tile::Tile;
 use bevy::prelude::*;
@@ -7,9 +5,13 @@ use indexmap::IndexSet;
 use serde::{Deserialize, Serialize};
 
 mod cache;
+pub mod generator;
+pub mod map_file;
 pub mod spawn_line;
 pub mod step;
 mod tile;
+pub mod transform;
+pub mod validation;
 pub mod visualisation;
 
 // Struct only for holding data, which can be de/serialized
@@ -49,63 +51,20 @@ impl Board {
     }
 
     pub fn get_tiles(&self, filter: Tile) -> IndexSet<UVec2> {
+        self.get_tiles_where(|tile| *tile == filter)
+    }
+
+    pub fn get_tiles_where(&self, filter: impl Fn(&Tile) -> bool) -> IndexSet<UVec2> {
         let mut tiles: IndexSet<UVec2> = IndexSet::new();
         for (y, row) in self.tiles.iter().enumerate() {
             for (x, tile) in row.iter().enumerate() {
-                if *tile == filter {
+                if filter(tile) {
                     tiles.insert(UVec2::new(x as u32, y as u32));
                 }
             }
         }
         tiles
     }
-
-    pub fn change_size(&mut self, new_width: u8, new_heigth: u8) {
-        // Add/reduce height
-        match new_heigth.cmp(&self.height) {
-            Ordering::Less => {
-                let to_del = self.height - new_heigth;
-                for _ in 0..to_del {
-                    self.tiles.pop();
-                }
-            }
-            Ordering::Equal => (),
-            Ordering::Greater => {
-                let to_add = new_heigth - self.height;
-                for _ in 0..to_add {
-                    let mut row = Vec::new();
-                    for _ in 0..self.width {
-                        row.push(Tile::Empty);
-                    }
-                    self.tiles.push(row);
-                }
-            }
-        }
-
-        // Add/reduce width
-        match new_width.cmp(&self.width) {
-            Ordering::Less => {
-                let to_del = self.width - new_width;
-                for row in &mut self.tiles {
-                    for _ in 0..to_del {
-                        row.pop();
-                    }
-                }
-            }
-            Ordering::Equal => (),
-            Ordering::Greater => {
-                let to_add = new_width - self.width;
-                for row in &mut self.tiles {
-                    for _ in 0..to_add {
-                        row.push(Tile::Empty);
-                    }
-                }
-            }
-        }
-
-        self.width = new_width;
-        self.height = new_heigth;
-    }
 }
 
 impl Default for Board {
@@ -2,20 +2,15 @@ use crate::utils::Vec2Board;
 use euclid::Angle;
 use serde::{Deserialize, Serialize};
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum BoardDirection {
+    #[default]
     East,
     North,
     West,
     South,
 }
 
-impl Default for BoardDirection {
-    fn default() -> Self {
-        Self::East
-    }
-}
-
 impl BoardDirection {
     pub fn inverted(self) -> Self {
         use BoardDirection::*;
@@ -37,6 +32,9 @@ impl BoardDirection {
     }
 }
 
+// Steps of one route from the road start to the road end
+pub type RoadPath = Vec<BoardStep>;
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct BoardStep {
     pub road_path_index: usize,
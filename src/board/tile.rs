@@ -1,3 +1,4 @@
+use crate::balance::{BOOST_PAD_SPEED_FACTOR, HIGH_GROUND_RANGE_FACTOR, MUD_SPEED_FACTOR};
 use serde::{Deserialize, Serialize};
 
 // Place on the board
@@ -15,10 +16,47 @@ pub enum Tile {
 
     // Blocked, unusable place
     Empty,
+
+    // Road which slows enemies down
+    Mud,
+
+    // Road which speeds enemies up
+    BoostPad,
+
+    // Tower ground with more range for the tower on it
+    HighGround,
+
+    // Blocked, only for the looks
+    Water,
+    Rock,
 }
 
 impl Tile {
     pub fn is_buildable(&self) -> bool {
-        matches!(self, Tile::TowerGround | Tile::BuildingGround)
+        self.is_tower_ground() || *self == Tile::BuildingGround
+    }
+
+    pub fn is_tower_ground(&self) -> bool {
+        matches!(self, Tile::TowerGround | Tile::HighGround)
+    }
+
+    // Enemies walk on every road kind, the road network doesn't care about the kind
+    pub fn is_road(&self) -> bool {
+        matches!(self, Tile::Road | Tile::Mud | Tile::BoostPad)
+    }
+
+    pub fn speed_factor(&self) -> f32 {
+        match self {
+            Tile::Mud => MUD_SPEED_FACTOR,
+            Tile::BoostPad => BOOST_PAD_SPEED_FACTOR,
+            _ => 1.,
+        }
+    }
+
+    pub fn range_factor(&self) -> f32 {
+        match self {
+            Tile::HighGround => HIGH_GROUND_RANGE_FACTOR,
+            _ => 1.,
+        }
     }
 }
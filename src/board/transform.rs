@@ -0,0 +1,149 @@
+use super::Board;
+use bevy::prelude::*;
+
+// Whole board operations, the content keeps its shape
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardTransform {
+    RotateClockwise,
+    MirrorHorizontally,
+    MirrorVertically,
+    // Tiles pushed over the edge are lost
+    Shift(IVec2),
+}
+
+// Part of the board that stays in place on resize
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResizeAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    #[default]
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl ResizeAnchor {
+    // Rows from top to bottom, as shown in the editor
+    pub const GRID: [[ResizeAnchor; 3]; 3] = [
+        [Self::TopLeft, Self::Top, Self::TopRight],
+        [Self::Left, Self::Center, Self::Right],
+        [Self::BottomLeft, Self::Bottom, Self::BottomRight],
+    ];
+
+    // Share of the size change that goes to the left and bottom, in halves
+    fn halves(&self) -> IVec2 {
+        use ResizeAnchor::*;
+        let x = match self {
+            TopLeft | Left | BottomLeft => 0,
+            Top | Center | Bottom => 1,
+            TopRight | Right | BottomRight => 2,
+        };
+        let y = match self {
+            BottomLeft | Bottom | BottomRight => 0,
+            Left | Center | Right => 1,
+            TopLeft | Top | TopRight => 2,
+        };
+        IVec2::new(x, y)
+    }
+}
+
+impl Board {
+    pub fn transform(&mut self, transform: BoardTransform) {
+        match transform {
+            BoardTransform::RotateClockwise => self.rotate_clockwise(),
+            BoardTransform::MirrorHorizontally => {
+                self.tiles.iter_mut().for_each(|row| row.reverse())
+            }
+            BoardTransform::MirrorVertically => self.tiles.reverse(),
+            BoardTransform::Shift(offset) => {
+                *self = self.copied_into(self.width, self.height, offset);
+            }
+        }
+    }
+
+    pub fn resize(&mut self, new_width: u8, new_height: u8, anchor: ResizeAnchor) {
+        let growth = IVec2::new(
+            new_width as i32 - self.width as i32,
+            new_height as i32 - self.height as i32,
+        );
+        *self = self.copied_into(new_width, new_height, growth * anchor.halves() / 2);
+    }
+
+    // y points up, so the top left tile ends up top right
+    fn rotate_clockwise(&mut self) {
+        let (width, height) = (self.width as usize, self.height as usize);
+        self.tiles = (0..width)
+            .map(|new_y| {
+                (0..height)
+                    .map(|new_x| self.tiles[new_x][width - 1 - new_y])
+                    .collect()
+            })
+            .collect();
+        (self.width, self.height) = (self.height, self.width);
+    }
+
+    fn copied_into(&self, width: u8, height: u8, offset: IVec2) -> Board {
+        let mut board = Board {
+            name: self.name.clone(),
+            ..Board::empty(width, height)
+        };
+        for (y, row) in self.tiles.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                let pos = IVec2::new(x as i32, y as i32) + offset;
+                if pos.x < 0 || pos.y < 0 {
+                    continue;
+                }
+                if let Some(new_tile) = board.get_tile_mut(&pos.as_uvec2()) {
+                    *new_tile = *tile;
+                }
+            }
+        }
+        board
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Tile;
+
+    fn board(rows: &[&[Tile]]) -> Board {
+        Board {
+            name: String::from("test"),
+            width: rows[0].len() as u8,
+            height: rows.len() as u8,
+            tiles: rows.iter().map(|row| row.to_vec()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_rotate_clockwise() {
+        use Tile::*;
+        let mut rotated = board(&[&[Road, Empty, Empty], &[TowerGround, Empty, Empty]]);
+        rotated.transform(BoardTransform::RotateClockwise);
+        // Top left tower ground goes top right, bottom left road goes top left
+        assert_eq!((rotated.width, rotated.height), (2, 3));
+        assert_eq!(rotated.tiles[2], vec![Road, TowerGround]);
+        for _ in 0..3 {
+            rotated.transform(BoardTransform::RotateClockwise);
+        }
+        assert_eq!(rotated.tiles[0], vec![Road, Empty, Empty]);
+    }
+
+    #[test]
+    fn test_resize_keeps_anchored_content() {
+        use Tile::*;
+        let mut resized = board(&[&[Road, TowerGround, Empty], &[Empty, BuildingGround, Empty]]);
+        resized.resize(5, 2, ResizeAnchor::Center);
+        assert_eq!(
+            resized.tiles[0],
+            vec![Empty, Road, TowerGround, Empty, Empty]
+        );
+        resized.resize(3, 1, ResizeAnchor::BottomRight);
+        assert_eq!(resized.tiles, vec![vec![TowerGround, Empty, Empty]]);
+    }
+}
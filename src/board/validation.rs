@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use std::{error::Error, fmt::Display, ops::Deref};
+
+// Problem of a map that makes it unplayable, with the tiles causing it
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    NoTowerTiles,
+    TooFewBuildingTiles(Vec<UVec2>),
+    TooFewRoadTiles(Vec<UVec2>),
+    NoRoadStart,
+    DisconnectedRoad(Vec<UVec2>),
+    DisconnectedBuildings(Vec<UVec2>),
+    WideRoad(Vec<UVec2>),
+    NoRoadEnd,
+    TooManyRoutes(usize),
+    DeadEndRoad(Vec<UVec2>),
+}
+
+impl ValidationError {
+    pub fn tiles(&self) -> &[UVec2] {
+        use ValidationError::*;
+        match self {
+            TooFewBuildingTiles(tiles)
+            | TooFewRoadTiles(tiles)
+            | DisconnectedRoad(tiles)
+            | DisconnectedBuildings(tiles)
+            | WideRoad(tiles)
+            | DeadEndRoad(tiles) => tiles,
+            NoTowerTiles | NoRoadStart | NoRoadEnd | TooManyRoutes(_) => &[],
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ValidationError::*;
+        match self {
+            NoTowerTiles => write!(f, "Need tower tiles"),
+            TooFewBuildingTiles(tiles) => {
+                write!(f, "Need minimal five building tiles, found {}", tiles.len())
+            }
+            TooFewRoadTiles(_) => write!(f, "Need minimal two road tiles"),
+            NoRoadStart => write!(f, "Need road starting point at the board edge"),
+            DisconnectedRoad(tiles) => write!(
+                f,
+                "All road tiles must be connected to each other, {} are not",
+                tiles.len()
+            ),
+            DisconnectedBuildings(tiles) => write!(
+                f,
+                "All building tiles must be connected to each other, {} are not",
+                tiles.len()
+            ),
+            WideRoad(_) => write!(f, "Road must be only one tile wide"),
+            NoRoadEnd => write!(f, "Road must end surrounded by three building tiles"),
+            TooManyRoutes(max) => write!(
+                f,
+                "Too many ways through the road network, maximal {max} allowed"
+            ),
+            DeadEndRoad(_) => write!(f, "Every road branch must lead to the road end"),
+        }
+    }
+}
+
+// All problems of a map at once, so the editor can show them together
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationErrors(pub Vec<ValidationError>);
+
+impl Deref for ValidationErrors {
+    type Target = Vec<ValidationError>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let texts: Vec<String> = self.iter().map(|err| err.to_string()).collect();
+        write!(f, "{}", texts.join("\n"))
+    }
+}
+
+impl Error for ValidationErrors {}
@@ -5,7 +5,10 @@ use crate::{
     utils::{towers::TowerRangeCircle, Vec2Board},
 };
 use bevy::{
-    color::palettes::css::{ANTIQUE_WHITE, DARK_GRAY, DIM_GRAY, GOLD, GRAY},
+    color::palettes::css::{
+        ANTIQUE_WHITE, DARK_GOLDENROD, DARK_GRAY, DIM_GRAY, GOLD, GRAY, LIGHT_SLATE_GRAY,
+        SADDLE_BROWN, STEEL_BLUE, TEAL,
+    },
     prelude::*,
     sprite::Anchor,
 };
@@ -145,12 +148,17 @@ impl<TScreen: Component + Default> BoardVisualisation<TScreen> {
         }
     }
 
-    fn get_tile_color(tile: Tile) -> Color {
+    pub fn get_tile_color(tile: Tile) -> Color {
         match tile {
             Tile::TowerGround => GOLD,
             Tile::BuildingGround => ANTIQUE_WHITE,
             Tile::Road => GRAY,
             Tile::Empty => DARK_GRAY,
+            Tile::Mud => SADDLE_BROWN,
+            Tile::BoostPad => TEAL,
+            Tile::HighGround => DARK_GOLDENROD,
+            Tile::Water => STEEL_BLUE,
+            Tile::Rock => LIGHT_SLATE_GRAY,
         }
         .into()
     }
@@ -178,7 +186,7 @@ impl<TScreen: Component + Default> BoardVisualisation<TScreen> {
 
     pub fn set_road_end_mark(&self, query: &mut RoadEndMarkQuery, board_cache: &BoardCache) {
         if let Some(end_pos) = board_cache.road_end_pos {
-            if let Some(last_step) = board_cache.road_path.last() {
+            if let Some(last_step) = board_cache.last_road_step() {
                 query
                     .iter_mut()
                     .for_each(|(mut visi, mut transform, comp)| {
@@ -274,9 +282,9 @@ mod road_end_mark {
         assets: &AssetServer,
     ) {
         let visibility =
-            visible(board_cache.road_path.last().is_some() && board_cache.road_end_pos.is_some());
+            visible(board_cache.last_road_step().is_some() && board_cache.road_end_pos.is_some());
 
-        let angle = if let Some(last_step) = board_cache.road_path.last() {
+        let angle = if let Some(last_step) = board_cache.last_road_step() {
             Angle::degrees(last_step.angle().to_degrees())
         } else {
             Angle::default()
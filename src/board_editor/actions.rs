@@ -1,13 +1,20 @@
-use super::{popups::Popups, zoom_cam_to_editor_board, BoardEditor, BoardVisu};
+use super::{
+    history::EditorHistory, popups::Popups, zoom_cam_to_editor_board, BoardEditor, BoardVisu,
+    EditorMapMeta,
+};
 use crate::{
     board::{
+        generator::{generate_board, GeneratorParams},
+        map_file::{save_board_to_file, MapFile, MapMeta},
+        transform::{BoardTransform, ResizeAnchor},
         visualisation::{BoardScreen, QueryBoardVisuTile, RoadEndMarkQuery},
         Board, BoardCache, Tile,
     },
-    utils::{save_board_to_file, GameState},
+    game::test_play::TestPlay,
+    utils::{Difficulty, GameState, Seed},
     CamMutQuery,
 };
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 
 #[derive(Message)]
 pub struct EditorSetTileMessage {
@@ -21,19 +28,34 @@ impl EditorSetTileMessage {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn on_set_tile(
     mut evr: MessageReader<EditorSetTileMessage>,
     mut editor: ResMut<BoardEditor>,
+    mut history: ResMut<EditorHistory>,
     mut board: ResMut<Board>,
     mut board_cache: ResMut<BoardCache>,
     mut q_board_visu_tile: QueryBoardVisuTile,
     mut q_road_end: RoadEndMarkQuery,
     visu: ResMut<BoardVisu>,
 ) {
+    let mut is_changed = false;
     for ev in evr.read() {
+        if board
+            .get_tile(&ev.pos)
+            .is_none_or(|tile| *tile == ev.tile_to)
+        {
+            continue;
+        }
+        history.record_stroke(&board);
         set_tile(&mut board, &mut board_cache, ev.pos, ev.tile_to);
-        validate_board(&mut editor, &board_cache);
         BoardVisu::change_tile(ev.pos, ev.tile_to, &mut q_board_visu_tile);
+        is_changed = true;
+    }
+    // Tools write a message per tile, the routes are searched once for all of them
+    if is_changed {
+        board_cache.calc_road_data(&board);
+        validate_board(&mut editor, &board_cache);
         visu.set_road_end_mark(&mut q_road_end, &board_cache)
     }
 }
@@ -43,7 +65,6 @@ fn set_tile(board: &mut Board, board_cache: &mut BoardCache, pos: UVec2, tile_to
         board_cache.remove_tile_pos(&pos, tile);
         board_cache.insert_tile_pos(pos, &tile_to);
         *tile = tile_to;
-        board_cache.calc_road_data(board);
     }
 }
 
@@ -54,12 +75,13 @@ pub(super) fn on_save_board(
     mut evr: MessageReader<EditorSaveBoardMessage>,
     mut popups: ResMut<Popups>,
     mut board: ResMut<Board>,
+    meta: Res<EditorMapMeta>,
 ) {
     for _ in evr.read() {
         if let Popups::Save(save_win) = &mut popups.as_mut() {
             save_win.err_text = None;
             board.name = save_win.map_file_name.clone();
-            match save_board_to_file(&save_win.map_file_name, &board) {
+            match save_board_to_file(&save_win.map_file_name, &board, &meta, save_win.format) {
                 Ok(()) => *popups = Popups::None,
                 Err(error) => save_win.err_text = Some(error.to_string()),
             }
@@ -68,31 +90,21 @@ pub(super) fn on_save_board(
 }
 
 #[derive(Message)]
-pub struct EditorLoadBoardMessage(pub Board);
+pub struct EditorLoadBoardMessage(pub MapFile);
 
-#[allow(clippy::too_many_arguments)]
 pub(super) fn on_load_board(
     mut evr: MessageReader<EditorLoadBoardMessage>,
-    mut cmds: Commands,
     mut popups: ResMut<Popups>,
-    mut board: ResMut<Board>,
-    mut board_cache: ResMut<BoardCache>,
-    mut editor: ResMut<BoardEditor>,
-    mut visu: ResMut<BoardVisu>,
-    mut q_cam: CamMutQuery,
-    q_screen: Query<Entity, With<BoardScreen>>,
-    q_win: Query<&Window>,
-    assets: Res<AssetServer>,
+    mut history: ResMut<EditorHistory>,
+    mut meta: ResMut<EditorMapMeta>,
+    mut editor_board: EditorBoard,
 ) {
     for ev in evr.read() {
         if let Popups::Load(_) = *popups {
-            *board_cache = BoardCache::new(&ev.0);
-            *board = ev.0.clone();
+            history.record(&editor_board.board);
+            **meta = ev.0.meta.clone();
+            editor_board.replace(ev.0.board.clone());
             *popups = Popups::None;
-            validate_board(&mut editor, &board_cache);
-            *visu = BoardVisu::new(0.9);
-            visu.repaint(&mut cmds, &q_screen, &board, &board_cache, &assets);
-            zoom_cam_to_editor_board(&board, &mut q_cam, q_win);
         }
     }
 }
@@ -112,28 +124,19 @@ impl EditorNewBoardMessage {
     }
 }
 
-#[allow(clippy::too_many_arguments)]
 pub(super) fn on_new_board(
     mut evr: MessageReader<EditorNewBoardMessage>,
-    mut cmds: Commands,
     mut popups: ResMut<Popups>,
-    mut board: ResMut<Board>,
-    mut board_cache: ResMut<BoardCache>,
-    mut visu: ResMut<BoardVisu>,
-    mut q_cam: CamMutQuery,
-    q_screen: Query<Entity, With<BoardScreen>>,
-    q_win: Query<&Window>,
-    assets: Res<AssetServer>,
+    mut history: ResMut<EditorHistory>,
+    mut meta: ResMut<EditorMapMeta>,
+    mut editor_board: EditorBoard,
 ) {
     for ev in evr.read() {
         if let Popups::New(_) = *popups {
-            let new_board = Board::empty(ev.width, ev.height);
-            *board_cache = BoardCache::new(&new_board);
-            *board = new_board;
+            history.record(&editor_board.board);
+            **meta = MapMeta::default();
+            editor_board.replace(Board::empty(ev.width, ev.height));
             *popups = Popups::None;
-            *visu = BoardVisu::new(0.9);
-            visu.repaint(&mut cmds, &q_screen, &board, &board_cache, &assets);
-            zoom_cam_to_editor_board(&board, &mut q_cam, q_win);
         }
     }
 }
@@ -142,40 +145,206 @@ pub(super) fn on_new_board(
 pub struct EditorEditBoardMessage {
     width: u8,
     height: u8,
+    anchor: ResizeAnchor,
 }
 
 impl EditorEditBoardMessage {
-    pub fn new(width: u8, lenght: u8) -> Self {
+    pub fn new(width: u8, lenght: u8, anchor: ResizeAnchor) -> Self {
         Self {
             width,
             height: lenght,
+            anchor,
         }
     }
 }
 
-#[allow(clippy::too_many_arguments)]
 pub(super) fn on_edit_board(
     mut evr: MessageReader<EditorEditBoardMessage>,
-    mut cmds: Commands,
     mut popups: ResMut<Popups>,
-    mut board: ResMut<Board>,
-    mut board_cache: ResMut<BoardCache>,
-    mut editor: ResMut<BoardEditor>,
-    mut visu: ResMut<BoardVisu>,
-    mut q_cam: CamMutQuery,
-    q_screen: Query<Entity, With<BoardScreen>>,
-    q_win: Query<&Window>,
-    assets: Res<AssetServer>,
+    mut history: ResMut<EditorHistory>,
+    mut editor_board: EditorBoard,
 ) {
     for ev in evr.read() {
         if let Popups::Edit(_) = *popups {
-            board.change_size(ev.width, ev.height);
-            *board_cache = BoardCache::new(&board);
+            history.record(&editor_board.board);
+            let mut board = editor_board.board.clone();
+            board.resize(ev.width, ev.height, ev.anchor);
+            editor_board.replace(board);
+            *popups = Popups::None;
+        }
+    }
+}
+
+#[derive(Message)]
+pub struct EditorTransformBoardMessage(pub BoardTransform);
+
+pub(super) fn on_transform_board(
+    mut evr: MessageReader<EditorTransformBoardMessage>,
+    mut history: ResMut<EditorHistory>,
+    mut editor_board: EditorBoard,
+) {
+    for ev in evr.read() {
+        history.record(&editor_board.board);
+        let mut board = editor_board.board.clone();
+        board.transform(ev.0);
+        editor_board.replace(board);
+    }
+}
+
+#[derive(Message)]
+pub struct EditorGenerateBoardMessage {
+    params: GeneratorParams,
+    seed: Seed,
+}
+
+impl EditorGenerateBoardMessage {
+    pub fn new(params: GeneratorParams, seed: Seed) -> Self {
+        Self { params, seed }
+    }
+}
+
+pub(super) fn on_generate_board(
+    mut evr: MessageReader<EditorGenerateBoardMessage>,
+    mut popups: ResMut<Popups>,
+    mut history: ResMut<EditorHistory>,
+    mut meta: ResMut<EditorMapMeta>,
+    mut editor_board: EditorBoard,
+) {
+    for ev in evr.read() {
+        if let Popups::Generate(generate_win) = &mut popups.as_mut() {
+            match generate_board(&ev.params, ev.seed) {
+                Ok(board) => {
+                    history.record(&editor_board.board);
+                    **meta = MapMeta::default();
+                    editor_board.replace(board);
+                    *popups = Popups::None;
+                }
+                Err(err) => generate_win.err_text = Some(err.to_string()),
+            }
+        }
+    }
+}
+
+// Board in ascii form from the clipboard
+#[derive(Message)]
+pub struct EditorPasteBoardMessage(pub String);
+
+pub(super) fn on_paste_board(
+    mut evr: MessageReader<EditorPasteBoardMessage>,
+    mut history: ResMut<EditorHistory>,
+    mut meta: ResMut<EditorMapMeta>,
+    mut editor_board: EditorBoard,
+) {
+    for ev in evr.read() {
+        match MapFile::from_ascii(&ev.0) {
+            Ok(map_file) => {
+                history.record(&editor_board.board);
+                **meta = map_file.meta;
+                editor_board.replace(map_file.board);
+            }
+            Err(err) => editor_board.editor.err_text = Some(format!("Paste failed: {err}")),
+        }
+    }
+}
+
+#[derive(Message)]
+pub struct EditorUndoMessage;
+
+pub(super) fn on_undo(
+    mut evr: MessageReader<EditorUndoMessage>,
+    mut history: ResMut<EditorHistory>,
+    mut editor_board: EditorBoard,
+) {
+    for _ in evr.read() {
+        if let Some(board) = history.undo(&editor_board.board) {
+            editor_board.replace(board);
+        }
+    }
+}
+
+#[derive(Message)]
+pub struct EditorRedoMessage;
+
+pub(super) fn on_redo(
+    mut evr: MessageReader<EditorRedoMessage>,
+    mut history: ResMut<EditorHistory>,
+    mut editor_board: EditorBoard,
+) {
+    for _ in evr.read() {
+        if let Some(board) = history.redo(&editor_board.board) {
+            editor_board.replace(board);
+        }
+    }
+}
+
+// Everything needed to swap the whole board of the editor
+#[derive(SystemParam)]
+pub(super) struct EditorBoard<'w, 's> {
+    cmds: Commands<'w, 's>,
+    board: ResMut<'w, Board>,
+    board_cache: ResMut<'w, BoardCache>,
+    editor: ResMut<'w, BoardEditor>,
+    visu: ResMut<'w, BoardVisu>,
+    q_cam: CamMutQuery<'w, 's, 'static>,
+    q_screen: Query<'w, 's, Entity, With<BoardScreen>>,
+    q_win: Query<'w, 's, &'static Window>,
+    assets: Res<'w, AssetServer>,
+}
+
+impl EditorBoard<'_, '_> {
+    fn replace(&mut self, board: Board) {
+        *self.board_cache = BoardCache::new(&board);
+        *self.board = board;
+        self.editor.err_text = None;
+        validate_board(&mut self.editor, &self.board_cache);
+        *self.visu = BoardVisu::new(0.9);
+        self.visu.repaint(
+            &mut self.cmds,
+            &self.q_screen,
+            &self.board,
+            &self.board_cache,
+            &self.assets,
+        );
+        zoom_cam_to_editor_board(&self.board, &mut self.q_cam, self.q_win.reborrow());
+    }
+}
+
+#[derive(Message)]
+pub struct EditorTestPlayMessage {
+    difficulty: Difficulty,
+    infinite_resources: bool,
+}
+
+impl EditorTestPlayMessage {
+    pub fn new(difficulty: Difficulty, infinite_resources: bool) -> Self {
+        Self {
+            difficulty,
+            infinite_resources,
+        }
+    }
+}
+
+pub(super) fn on_test_play(
+    mut evr: MessageReader<EditorTestPlayMessage>,
+    mut cmds: Commands,
+    mut popups: ResMut<Popups>,
+    mut set_game_state: ResMut<NextState<GameState>>,
+    board: Res<Board>,
+    board_cache: Res<BoardCache>,
+) {
+    for ev in evr.read() {
+        if let Popups::TestPlay(test_play_win) = &mut popups.as_mut() {
+            if let Err(errors) = board_cache.validate() {
+                test_play_win.err_text = Some(errors.to_string());
+                continue;
+            }
+            cmds.insert_resource(TestPlay {
+                board: board.clone(),
+                difficulty: ev.difficulty,
+                infinite_resources: ev.infinite_resources,
+            });
             *popups = Popups::None;
-            validate_board(&mut editor, &board_cache);
-            *visu = BoardVisu::new(0.9);
-            visu.repaint(&mut cmds, &q_screen, &board, &board_cache, &assets);
-            zoom_cam_to_editor_board(&board, &mut q_cam, q_win);
+            set_game_state.set(GameState::Game);
         }
     }
 }
@@ -193,8 +362,8 @@ pub(super) fn on_leave(
 }
 
 fn validate_board(editor: &mut BoardEditor, board_cache: &BoardCache) {
-    editor.err_text = match board_cache.validate() {
-        Ok(_) => None,
-        Err(err) => Some(String::from(err)),
+    editor.errors = match board_cache.validate() {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors.0,
     };
 }
@@ -1,34 +1,112 @@
-use super::{actions::EditorSetTileMessage, popups::Popups, side_bar::SettileState};
-use crate::{board::Tile, utils::cursor_pos, CamQuery};
+use super::{
+    actions::{EditorRedoMessage, EditorSetTileMessage, EditorUndoMessage},
+    history::EditorHistory,
+    popups::Popups,
+    side_bar::SettileState,
+    tools::{ShapeDrag, ToolState},
+};
+use crate::{
+    board::{Board, Tile},
+    utils::cursor_pos,
+    CamQuery,
+};
 use bevy::prelude::*;
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn mouse_input(
-    set_tile_ev: MessageWriter<EditorSetTileMessage>,
+    mut cmds: Commands,
+    mut set_tile_ev: MessageWriter<EditorSetTileMessage>,
     set_tile_state: Res<State<SettileState>>,
+    tool_state: Res<State<ToolState>>,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
+    mut history: ResMut<EditorHistory>,
+    drag: Option<ResMut<ShapeDrag>>,
+    board: Res<Board>,
     popups: Res<Popups>,
     wnds: Query<&Window>,
     q_cam: CamQuery,
 ) {
+    // A new press is a new undo step
+    if mouse_button_input.any_just_pressed([MouseButton::Left, MouseButton::Right]) {
+        history.end_stroke();
+    }
     if popups.is_open() {
+        if drag.is_some() {
+            cmds.remove_resource::<ShapeDrag>();
+        }
         return;
     }
-    if mouse_button_input.pressed(MouseButton::Left) {
-        send_set_tile_event(set_tile_ev, wnds, q_cam, set_tile_state.as_tile());
+    let tool = *tool_state.get();
+    let pos = board_tile_pos(&board, wnds, q_cam);
+
+    // Shapes get painted when the mouse button is released
+    if let Some(mut drag) = drag {
+        if let Some(pos) = pos.filter(|pos| *pos != drag.end) {
+            drag.end = pos;
+        }
+        if !mouse_button_input.any_pressed([MouseButton::Left, MouseButton::Right]) {
+            for pos in tool.tiles(&board, drag.start, drag.end) {
+                set_tile_ev.write(EditorSetTileMessage::new(pos, drag.tile));
+            }
+            cmds.remove_resource::<ShapeDrag>();
+        }
+        return;
+    }
+
+    let Some(pos) = pos else { return };
+    let tile = if mouse_button_input.pressed(MouseButton::Left) {
+        match tool {
+            // Road kinds keep their kind, so mud paths can be drawn too
+            ToolState::RoadPath if !set_tile_state.as_tile().is_road() => Tile::Road,
+            _ => set_tile_state.as_tile(),
+        }
     } else if mouse_button_input.pressed(MouseButton::Right) {
-        send_set_tile_event(set_tile_ev, wnds, q_cam, Tile::Empty);
+        Tile::Empty
+    } else {
+        return;
+    };
+    let is_just_pressed =
+        mouse_button_input.any_just_pressed([MouseButton::Left, MouseButton::Right]);
+    match tool {
+        ToolState::Freehand => {
+            set_tile_ev.write(EditorSetTileMessage::new(pos, tile));
+        }
+        ToolState::FloodFill if is_just_pressed => {
+            for pos in tool.tiles(&board, pos, pos) {
+                set_tile_ev.write(EditorSetTileMessage::new(pos, tile));
+            }
+        }
+        _ if tool.is_shape() && is_just_pressed => cmds.insert_resource(ShapeDrag {
+            start: pos,
+            end: pos,
+            tile,
+        }),
+        _ => (),
     }
 }
 
-fn send_set_tile_event(
-    mut set_tile_ev: MessageWriter<EditorSetTileMessage>,
-    q_win: Query<&Window>,
-    q_cam: CamQuery,
-    tile: Tile,
+fn board_tile_pos(board: &Board, q_win: Query<&Window>, q_cam: CamQuery) -> Option<UVec2> {
+    let pos = cursor_pos(q_win, q_cam)?;
+    if pos.x < 0. || pos.y < 0. {
+        return None;
+    }
+    let pos = pos.as_uvec2();
+    board.get_tile(&pos).map(|_| pos)
+}
+
+pub(super) fn keyboard_input(
+    mut undo_ev: MessageWriter<EditorUndoMessage>,
+    mut redo_ev: MessageWriter<EditorRedoMessage>,
+    keys: Res<ButtonInput<KeyCode>>,
+    popups: Res<Popups>,
 ) {
-    if let Some(pos) = cursor_pos(q_win, q_cam) {
-        if pos.x >= 0. && pos.y >= 0. {
-            set_tile_ev.write(EditorSetTileMessage::new(pos.as_uvec2(), tile));
-        }
+    if popups.is_open() || !keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    let is_shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if keys.just_pressed(KeyCode::KeyY) || (is_shift && keys.just_pressed(KeyCode::KeyZ)) {
+        redo_ev.write(EditorRedoMessage);
+    } else if keys.just_pressed(KeyCode::KeyZ) {
+        undo_ev.write(EditorUndoMessage);
     }
 }
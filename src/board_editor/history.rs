@@ -0,0 +1,89 @@
+use crate::board::Board;
+use bevy::prelude::*;
+
+// Oldest steps get dropped beyond this
+const MAX_HISTORY_STEPS: usize = 100;
+
+// Board snapshots before each change, boards are small enough to copy whole
+#[derive(Resource, Default)]
+pub(super) struct EditorHistory {
+    undo_stack: Vec<Board>,
+    redo_stack: Vec<Board>,
+    is_stroke_open: bool,
+}
+
+impl EditorHistory {
+    pub fn record(&mut self, board: &Board) {
+        self.undo_stack.push(board.clone());
+        if self.undo_stack.len() > MAX_HISTORY_STEPS {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    // All tiles painted while the mouse button is held are one step
+    pub fn record_stroke(&mut self, board: &Board) {
+        if !self.is_stroke_open {
+            self.record(board);
+            self.is_stroke_open = true;
+        }
+    }
+
+    pub fn end_stroke(&mut self) {
+        self.is_stroke_open = false;
+    }
+
+    pub fn undo(&mut self, current: &Board) -> Option<Board> {
+        self.end_stroke();
+        let board = self.undo_stack.pop()?;
+        self.redo_stack.push(current.clone());
+        Some(board)
+    }
+
+    pub fn redo(&mut self, current: &Board) -> Option<Board> {
+        self.end_stroke();
+        let board = self.redo_stack.pop()?;
+        self.undo_stack.push(current.clone());
+        Some(board)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Tile;
+
+    fn painted(board: &Board, x: u32) -> Board {
+        let mut board = board.clone();
+        *board.get_tile_mut(&UVec2::new(x, 0)).unwrap() = Tile::Road;
+        board
+    }
+
+    #[test]
+    fn test_stroke_is_one_undo_step() {
+        let mut history = EditorHistory::default();
+        let empty = Board::empty(3, 1);
+        history.record_stroke(&empty);
+        let one = painted(&empty, 0);
+        history.record_stroke(&one);
+        let two = painted(&one, 1);
+        history.end_stroke();
+        history.record_stroke(&two);
+        let three = painted(&two, 2);
+
+        assert_eq!(history.undo(&three).unwrap().tiles, two.tiles);
+        assert_eq!(history.undo(&two).unwrap().tiles, empty.tiles);
+        assert!(!history.can_undo());
+        assert_eq!(history.redo(&empty).unwrap().tiles, two.tiles);
+        assert_eq!(history.redo(&two).unwrap().tiles, three.tiles);
+        assert!(!history.can_redo());
+    }
+}
@@ -1,18 +1,27 @@
 use self::{
     actions::{
-        EditorEditBoardMessage, EditorLeaveMessage, EditorLoadBoardMessage, EditorNewBoardMessage,
-        EditorSaveBoardMessage, EditorSetTileMessage,
+        EditorEditBoardMessage, EditorGenerateBoardMessage, EditorLeaveMessage,
+        EditorLoadBoardMessage, EditorNewBoardMessage, EditorPasteBoardMessage, EditorRedoMessage,
+        EditorSaveBoardMessage, EditorSetTileMessage, EditorTestPlayMessage,
+        EditorTransformBoardMessage, EditorUndoMessage,
     },
-    controls::mouse_input,
+    controls::{keyboard_input, mouse_input},
+    history::EditorHistory,
+    overlay::{draw_tool_preview, draw_validation_overlay},
     popups::{
-        add_edit_board_window, add_load_board_window, add_new_board_window, add_save_board_window,
-        Popups,
+        add_edit_board_window, add_generate_board_window, add_load_board_window,
+        add_new_board_window, add_save_board_window, add_test_play_window, Popups,
     },
     side_bar::{add_side_bar, SettileState},
+    tools::{ShapeDrag, ToolState},
     top_bar::add_top_menu_bar,
 };
 use crate::{
-    board::{visualisation::BoardVisualisation, Board, BoardCache},
+    board::{
+        map_file::MapMeta, validation::ValidationError, visualisation::BoardVisualisation, Board,
+        BoardCache,
+    },
+    game::test_play::TestPlay,
     utils::{despawn_all_of, zoom_cam_to_board_with_viewport_padding, GameState},
     CamMutQuery,
 };
@@ -21,8 +30,11 @@ use bevy_egui::EguiPrimaryContextPass;
 
 mod actions;
 mod controls;
+mod history;
+mod overlay;
 mod popups;
 mod side_bar;
+mod tools;
 mod top_bar;
 
 type BoardVisu = BoardVisualisation<BoardEditorScreen>;
@@ -36,9 +48,15 @@ const LEFT_BAR_WIDTH_PX: f32 = 140.0;
 
 #[derive(Resource, Default)]
 struct BoardEditor {
+    errors: Vec<ValidationError>,
+    // Problems outside the board itself, like a failed paste
     err_text: Option<String>,
 }
 
+// Author and description of the edited map, written back on save
+#[derive(Resource, Default, Deref, DerefMut)]
+struct EditorMapMeta(MapMeta);
+
 // This plugin will contain the map editor
 pub struct BoardEditorPlugin;
 
@@ -50,19 +68,35 @@ impl Plugin for BoardEditorPlugin {
             .add_message::<EditorNewBoardMessage>()
             .add_message::<EditorEditBoardMessage>()
             .add_message::<EditorLeaveMessage>()
+            .add_message::<EditorUndoMessage>()
+            .add_message::<EditorRedoMessage>()
+            .add_message::<EditorTestPlayMessage>()
+            .add_message::<EditorGenerateBoardMessage>()
+            .add_message::<EditorPasteBoardMessage>()
+            .add_message::<EditorTransformBoardMessage>()
             .init_state::<SettileState>()
+            .init_state::<ToolState>()
             .add_systems(OnEnter(GameState::MapEditor), editor_setup)
             .add_systems(
                 Update,
                 (
                     on_resize,
                     mouse_input,
+                    keyboard_input,
                     actions::on_set_tile,
                     actions::on_save_board,
                     actions::on_load_board,
                     actions::on_new_board,
                     actions::on_edit_board,
+                    actions::on_undo,
+                    actions::on_redo,
+                    actions::on_test_play,
+                    actions::on_generate_board,
+                    actions::on_paste_board,
+                    actions::on_transform_board,
                     actions::on_leave,
+                    draw_validation_overlay.run_if(resource_changed::<BoardEditor>),
+                    draw_tool_preview.after(mouse_input),
                 )
                     .run_if(in_state(GameState::MapEditor)),
             )
@@ -75,6 +109,8 @@ impl Plugin for BoardEditorPlugin {
                     add_save_board_window,
                     add_new_board_window,
                     add_edit_board_window,
+                    add_test_play_window,
+                    add_generate_board_window,
                 )
                     .run_if(in_state(GameState::MapEditor)),
             )
@@ -90,9 +126,21 @@ fn editor_setup(
     mut q_cam: CamMutQuery,
     q_win: Query<&Window>,
     assets: Res<AssetServer>,
+    test_play: Option<Res<TestPlay>>,
 ) {
-    let board = Board::default();
+    // Back from a test play, continue with its board
+    let board = match test_play {
+        Some(test_play) => {
+            cmds.remove_resource::<TestPlay>();
+            test_play.board.clone()
+        }
+        None => Board::default(),
+    };
     let board_cache = BoardCache::new(&board);
+    let mut editor = BoardEditor::default();
+    if let Err(errors) = board_cache.validate() {
+        editor.errors = errors.0;
+    }
 
     zoom_cam_to_editor_board(&board, &mut q_cam, q_win);
     let visu = BoardVisu::new(0.9);
@@ -100,7 +148,9 @@ fn editor_setup(
     cmds.insert_resource(visu);
     cmds.insert_resource(board);
     cmds.insert_resource(board_cache);
-    cmds.init_resource::<BoardEditor>();
+    cmds.insert_resource(editor);
+    cmds.init_resource::<EditorHistory>();
+    cmds.init_resource::<EditorMapMeta>();
     cmds.insert_resource(Popups::None);
 }
 
@@ -128,9 +178,15 @@ pub(super) fn zoom_cam_to_editor_board(
     );
 }
 
-fn clean_up_editor(mut commands: Commands) {
+fn clean_up_editor(mut commands: Commands, test_play: Option<Res<TestPlay>>) {
     commands.remove_resource::<BoardEditor>();
+    // Undo history and map meta survive a test play
+    if test_play.is_none() {
+        commands.remove_resource::<EditorHistory>();
+        commands.remove_resource::<EditorMapMeta>();
+    }
     commands.remove_resource::<Board>();
     commands.remove_resource::<BoardCache>();
     commands.remove_resource::<Popups>();
+    commands.remove_resource::<ShapeDrag>();
 }
@@ -0,0 +1,74 @@
+use super::{
+    tools::{ShapeDrag, ToolState},
+    BoardEditor, BoardEditorScreen, BoardVisu,
+};
+use crate::{board::Board, utils::Vec2Board};
+use bevy::{prelude::*, sprite::Anchor};
+
+#[derive(Component)]
+pub(super) struct ValidationOverlayTile;
+
+// Marks the tiles of all validation errors, redrawn whenever the errors change
+pub(super) fn draw_validation_overlay(
+    mut cmds: Commands,
+    editor: Res<BoardEditor>,
+    visu: Res<BoardVisu>,
+    q_overlay: Query<Entity, With<ValidationOverlayTile>>,
+) {
+    for entity in q_overlay.iter() {
+        cmds.entity(entity).try_despawn();
+    }
+    for pos in editor.errors.iter().flat_map(|err| err.tiles()) {
+        cmds.spawn((
+            Sprite {
+                custom_size: Some(Vec2::splat(visu.inner_tile_size)),
+                color: Color::srgba(0.9, 0.1, 0.1, 0.5),
+                ..Default::default()
+            },
+            Anchor::BOTTOM_LEFT,
+            Transform {
+                translation: Vec2Board::from(*pos).to_scaled_vec3(1.),
+                ..Default::default()
+            },
+            ValidationOverlayTile,
+            BoardEditorScreen,
+        ));
+    }
+}
+
+#[derive(Component)]
+pub(super) struct ToolPreviewTile;
+
+// Shows the tiles the dragged shape will paint
+pub(super) fn draw_tool_preview(
+    mut cmds: Commands,
+    drag: Option<Res<ShapeDrag>>,
+    tool_state: Res<State<ToolState>>,
+    board: Res<Board>,
+    visu: Res<BoardVisu>,
+    q_preview: Query<Entity, With<ToolPreviewTile>>,
+) {
+    if drag.as_ref().is_some_and(|drag| !drag.is_changed()) {
+        return;
+    }
+    for entity in q_preview.iter() {
+        cmds.entity(entity).try_despawn();
+    }
+    let Some(drag) = drag else { return };
+    for pos in tool_state.get().tiles(&board, drag.start, drag.end) {
+        cmds.spawn((
+            Sprite {
+                custom_size: Some(Vec2::splat(visu.inner_tile_size)),
+                color: BoardVisu::get_tile_color(drag.tile).with_alpha(0.7),
+                ..Default::default()
+            },
+            Anchor::BOTTOM_LEFT,
+            Transform {
+                translation: Vec2Board::from(pos).to_scaled_vec3(0.5),
+                ..Default::default()
+            },
+            ToolPreviewTile,
+            BoardEditorScreen,
+        ));
+    }
+}
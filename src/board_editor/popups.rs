@@ -1,15 +1,22 @@
 use super::actions::{
-    EditorEditBoardMessage, EditorLoadBoardMessage, EditorNewBoardMessage, EditorSaveBoardMessage,
+    EditorEditBoardMessage, EditorGenerateBoardMessage, EditorLoadBoardMessage,
+    EditorNewBoardMessage, EditorSaveBoardMessage, EditorTestPlayMessage,
 };
 use crate::{
-    board::Board,
-    utils::{add_error_box, add_row, get_all_boards_in_folder},
+    board::{
+        generator::{GeneratorParams, MIN_GENERATED_BOARD_SIZE},
+        map_file::{get_all_map_files_in_folder, MapFile, MapFormat},
+        transform::ResizeAnchor,
+        Board,
+    },
+    utils::{add_error_box, add_row, random_seed, Difficulty, Seed},
 };
 use bevy::prelude::*;
 use bevy_egui::{
-    egui::{self, TextEdit},
+    egui::{self, Checkbox, ComboBox, TextEdit},
     EguiContexts,
 };
+use strum::IntoEnumIterator;
 
 #[derive(Resource)]
 pub(super) enum Popups {
@@ -17,6 +24,8 @@ pub(super) enum Popups {
     Save(SaveBoardWindow),
     New(NewBoardWindow),
     Edit(EditBoardWindow),
+    TestPlay(TestPlayWindow),
+    Generate(GenerateBoardWindow),
     None,
 }
 
@@ -43,6 +52,7 @@ impl Default for NewBoardWindow {
 pub(super) struct EditBoardWindow {
     width: u8,
     height: u8,
+    anchor: ResizeAnchor,
 }
 
 impl EditBoardWindow {
@@ -50,33 +60,67 @@ impl EditBoardWindow {
         Self {
             width: board.width,
             height: board.height,
+            anchor: ResizeAnchor::default(),
         }
     }
 }
 
 pub(super) struct LoadBoardWindow {
-    boards: Vec<Board>,
+    map_files: Vec<MapFile>,
     err_text: Option<String>,
 }
 
 impl Default for LoadBoardWindow {
     fn default() -> Self {
-        match get_all_boards_in_folder() {
-            Ok(boards) => Self {
-                boards,
-                err_text: None,
+        match get_all_map_files_in_folder() {
+            Ok((map_files, errors)) => Self {
+                map_files,
+                err_text: (!errors.is_empty()).then(|| errors.join("\n")),
             },
             Err(err) => Self {
-                boards: Vec::new(),
+                map_files: Vec::new(),
                 err_text: Some(err.to_string()),
             },
         }
     }
 }
 
+pub(super) struct TestPlayWindow {
+    difficulty: Difficulty,
+    infinite_resources: bool,
+    pub err_text: Option<String>,
+}
+
+impl Default for TestPlayWindow {
+    fn default() -> Self {
+        Self {
+            difficulty: Difficulty::Easy,
+            infinite_resources: true,
+            err_text: None,
+        }
+    }
+}
+
+pub(super) struct GenerateBoardWindow {
+    params: GeneratorParams,
+    seed_text: String,
+    pub err_text: Option<String>,
+}
+
+impl Default for GenerateBoardWindow {
+    fn default() -> Self {
+        Self {
+            params: GeneratorParams::default(),
+            seed_text: random_seed().to_string(),
+            err_text: None,
+        }
+    }
+}
+
 #[derive(Default)]
 pub(super) struct SaveBoardWindow {
     pub map_file_name: String,
+    pub format: MapFormat,
     pub err_text: Option<String>,
 }
 
@@ -93,6 +137,11 @@ pub(super) fn add_save_board_window(
                 TextEdit::singleline(&mut popup.map_file_name).margin(egui::Vec2::new(10., 16.)),
                 ui,
             );
+            ui.horizontal(|ui| {
+                ui.add_sized([200., 60.], egui::Label::new("Format"));
+                ui.radio_value(&mut popup.format, MapFormat::Json, "JSON");
+                ui.radio_value(&mut popup.format, MapFormat::Ascii, "ASCII");
+            });
             ui.add_space(10.);
 
             // Ok/Cancel Buttons
@@ -148,15 +197,15 @@ fn add_load_board_select(
     mut load_ev: MessageWriter<EditorLoadBoardMessage>,
 ) {
     egui::containers::ScrollArea::vertical().show(ui, |ui| {
-        for board in &load_win.boards {
+        for map_file in &load_win.map_files {
             if ui
                 .add_sized(
                     [400., 60.],
-                    bevy_egui::egui::widgets::Button::new(&board.name),
+                    bevy_egui::egui::widgets::Button::new(&map_file.board.name),
                 )
                 .clicked()
             {
-                load_ev.write(EditorLoadBoardMessage(board.clone()));
+                load_ev.write(EditorLoadBoardMessage(map_file.clone()));
                 break;
             }
         }
@@ -170,8 +219,13 @@ pub(super) fn add_new_board_window(
 ) {
     let mut is_close = false;
     if let Popups::New(popup) = &mut *popup {
-        let (is_ok, is_cancel) =
-            add_new_edit_popup(egui_ctx, &mut popup.width, &mut popup.height, "New map");
+        let (is_ok, is_cancel) = add_new_edit_popup(
+            egui_ctx,
+            &mut popup.width,
+            &mut popup.height,
+            None,
+            "New map",
+        );
         if is_ok {
             new_board_ev.write(EditorNewBoardMessage::new(popup.width, popup.height));
         } else if is_cancel {
@@ -190,10 +244,19 @@ pub(super) fn add_edit_board_window(
 ) {
     let mut is_close = false;
     if let Popups::Edit(popup) = &mut *popup {
-        let (is_ok, is_cancel) =
-            add_new_edit_popup(egui_ctx, &mut popup.width, &mut popup.height, "Edit size");
+        let (is_ok, is_cancel) = add_new_edit_popup(
+            egui_ctx,
+            &mut popup.width,
+            &mut popup.height,
+            Some(&mut popup.anchor),
+            "Edit size",
+        );
         if is_ok {
-            edit_ev.write(EditorEditBoardMessage::new(popup.width, popup.height));
+            edit_ev.write(EditorEditBoardMessage::new(
+                popup.width,
+                popup.height,
+                popup.anchor,
+            ));
         } else if is_cancel {
             is_close = true;
         }
@@ -203,10 +266,135 @@ pub(super) fn add_edit_board_window(
     }
 }
 
+pub(super) fn add_test_play_window(
+    mut egui_ctx: EguiContexts,
+    mut popup: ResMut<Popups>,
+    mut test_play_ev: MessageWriter<EditorTestPlayMessage>,
+) {
+    let mut is_close = false;
+    if let Popups::TestPlay(popup) = &mut *popup {
+        add_popup_window(&mut egui_ctx, "Test play", |ui| {
+            ui.horizontal(|ui| {
+                ui.add_sized([200., 60.], egui::Label::new("Difficulty"));
+                ComboBox::from_id_salt("test_play_difficulty")
+                    .selected_text(popup.difficulty.to_string())
+                    .show_ui(ui, |ui| {
+                        for difficulty in Difficulty::iter() {
+                            ui.selectable_value(
+                                &mut popup.difficulty,
+                                difficulty,
+                                difficulty.to_string(),
+                            );
+                        }
+                    });
+            });
+            add_row(
+                "Infinite resources",
+                Checkbox::without_text(&mut popup.infinite_resources),
+                ui,
+            );
+
+            // Ok/Cancel Buttons
+            ui.add_space(10.);
+            let (is_ok, is_cancel) = add_ok_cancel_row(ui);
+            if is_ok {
+                test_play_ev.write(EditorTestPlayMessage::new(
+                    popup.difficulty,
+                    popup.infinite_resources,
+                ));
+            } else if is_cancel {
+                is_close = true;
+            }
+
+            // Error container
+            if let Some(err_text) = &popup.err_text {
+                add_error_box(err_text, ui);
+            }
+        });
+    }
+    if is_close {
+        *popup = Popups::None;
+    }
+}
+
+pub(super) fn add_generate_board_window(
+    mut egui_ctx: EguiContexts,
+    mut popup: ResMut<Popups>,
+    mut generate_ev: MessageWriter<EditorGenerateBoardMessage>,
+) {
+    let mut is_close = false;
+    if let Popups::Generate(popup) = &mut *popup {
+        add_popup_window(&mut egui_ctx, "Generate map", |ui| {
+            let params = &mut popup.params;
+            add_slider_row(
+                "Width",
+                &mut params.width,
+                MIN_GENERATED_BOARD_SIZE..=32,
+                ui,
+            );
+            add_slider_row(
+                "Height",
+                &mut params.height,
+                MIN_GENERATED_BOARD_SIZE..=32,
+                ui,
+            );
+            add_slider_row("Road length", &mut params.road_length, 3..=80, ui);
+            add_slider_row("Bends", &mut params.bends, 0..=20, ui);
+            add_slider_row("Building tiles", &mut params.building_size, 9..=40, ui);
+            add_slider_row("Tower ground", &mut params.tower_density, 0.0..=1.0, ui);
+            add_row(
+                "Seed",
+                TextEdit::singleline(&mut popup.seed_text).margin(egui::Vec2::new(10., 16.)),
+                ui,
+            );
+
+            // Ok/Cancel Buttons
+            ui.add_space(10.);
+            let (is_ok, is_cancel) = add_ok_cancel_row(ui);
+            if is_ok {
+                match popup.seed_text.trim().parse::<Seed>() {
+                    Ok(seed) => {
+                        generate_ev.write(EditorGenerateBoardMessage::new(params.clone(), seed));
+                    }
+                    Err(_) => {
+                        popup.err_text = Some(format!(
+                            "Seed must be a positive number, not '{}'",
+                            popup.seed_text
+                        ))
+                    }
+                }
+            } else if is_cancel {
+                is_close = true;
+            }
+
+            // Error container
+            if let Some(err_text) = &popup.err_text {
+                add_error_box(err_text, ui);
+            }
+        });
+    }
+    if is_close {
+        *popup = Popups::None;
+    }
+}
+
+fn add_slider_row<Num: egui::emath::Numeric>(
+    label: &str,
+    value: &mut Num,
+    range: std::ops::RangeInclusive<Num>,
+    ui: &mut egui::Ui,
+) {
+    let slider = egui::Slider::new(value, range)
+        .show_value(true)
+        .clamping(egui::SliderClamping::Always);
+    add_row(label, slider, ui);
+}
+
 pub(super) fn add_new_edit_popup(
     mut egui_ctx: EguiContexts,
     width: &mut u8,
     height: &mut u8,
+    anchor: Option<&mut ResizeAnchor>,
     title: &str,
 ) -> (bool, bool) {
     let (mut is_ok, mut is_cancel) = (false, false);
@@ -223,6 +411,27 @@ pub(super) fn add_new_edit_popup(
             .clamping(egui::SliderClamping::Always);
         add_row("Height", height_silder, ui);
 
+        // Part of the board which stays in place
+        if let Some(anchor) = anchor {
+            ui.horizontal(|ui| {
+                ui.add_sized([200., 60.], egui::Label::new("Anchor"));
+                egui::Grid::new("resize_anchor").show(ui, |ui| {
+                    for row in ResizeAnchor::GRID {
+                        for cell in row {
+                            let is_selected = *anchor == cell;
+                            if ui
+                                .add_sized([24., 24.], egui::Button::selectable(is_selected, ""))
+                                .clicked()
+                            {
+                                *anchor = cell;
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+        }
+
         // Ok/Cancel Buttons
         ui.add_space(10.);
         (is_ok, is_cancel) = add_ok_cancel_row(ui);
@@ -256,7 +465,9 @@ fn add_popup_window<R>(
 ) {
     let Ok(ctx) = egui_ctx.ctx_mut() else { return };
     bevy_egui::egui::Window::new(title)
-        .fixed_size((400., 200.))
+        .resizable(false)
+        .min_size((400., 200.))
+        .max_width(400.)
         .collapsible(false)
         .anchor(bevy_egui::egui::Align2::CENTER_CENTER, (0., 0.))
         .show(ctx, |ui| {
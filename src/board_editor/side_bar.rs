@@ -1,8 +1,13 @@
-use super::LEFT_BAR_WIDTH_PX;
-use crate::board::Tile;
-use bevy::prelude::*;
+use super::{
+    actions::EditorTransformBoardMessage, tools::ToolState, BoardEditor, LEFT_BAR_WIDTH_PX,
+};
+use crate::{
+    board::{transform::BoardTransform, Tile},
+    utils::add_error_box,
+};
+use bevy::{prelude::*, state::state::FreelyMutableState};
 use bevy_egui::{
-    egui::{self, Label, RadioButton, SidePanel},
+    egui::{self, Button, DragValue, Label, RadioButton, ScrollArea, SidePanel},
     EguiContexts,
 };
 
@@ -12,6 +17,11 @@ pub(super) enum SettileState {
     TowerGround,
     BuildingGround,
     Road,
+    Mud,
+    BoostPad,
+    HighGround,
+    Water,
+    Rock,
 }
 
 impl SettileState {
@@ -20,14 +30,25 @@ impl SettileState {
             SettileState::TowerGround => Tile::TowerGround,
             SettileState::BuildingGround => Tile::BuildingGround,
             SettileState::Road => Tile::Road,
+            SettileState::Mud => Tile::Mud,
+            SettileState::BoostPad => Tile::BoostPad,
+            SettileState::HighGround => Tile::HighGround,
+            SettileState::Water => Tile::Water,
+            SettileState::Rock => Tile::Rock,
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn add_side_bar(
     mut egui_ctx: EguiContexts,
     mut set_tile_state: ResMut<NextState<SettileState>>,
     tile_state: Res<State<SettileState>>,
+    mut set_tool_state: ResMut<NextState<ToolState>>,
+    tool_state: Res<State<ToolState>>,
+    editor: Res<BoardEditor>,
+    mut transform_ev: MessageWriter<EditorTransformBoardMessage>,
+    mut shift_by: Local<ShiftBy>,
 ) {
     let Ok(ctx) = egui_ctx.ctx_mut() else { return };
     SidePanel::left("map_editor_left_bar")
@@ -35,34 +56,99 @@ pub(super) fn add_side_bar(
         .default_width(LEFT_BAR_WIDTH_PX)
         .show(ctx, |ui| {
             ui.add_sized([LEFT_BAR_WIDTH_PX - 20., 40.], Label::new("tile type"));
-            add_tile_radio_button(
-                &mut set_tile_state,
-                &tile_state,
-                SettileState::TowerGround,
-                "Tower",
-                ui,
-            );
-            add_tile_radio_button(
-                &mut set_tile_state,
-                &tile_state,
-                SettileState::BuildingGround,
-                "Building",
-                ui,
-            );
-            add_tile_radio_button(
-                &mut set_tile_state,
-                &tile_state,
-                SettileState::Road,
-                "Road",
-                ui,
-            );
+            for (tile, text) in [
+                (SettileState::TowerGround, "Tower"),
+                (SettileState::BuildingGround, "Building"),
+                (SettileState::Road, "Road"),
+                (SettileState::Mud, "Mud (slow)"),
+                (SettileState::BoostPad, "Boost pad (fast)"),
+                (SettileState::HighGround, "High ground"),
+                (SettileState::Water, "Water"),
+                (SettileState::Rock, "Rock"),
+            ] {
+                add_state_radio_button(&mut set_tile_state, &tile_state, tile, text, ui);
+            }
+
+            ui.add_sized([LEFT_BAR_WIDTH_PX - 20., 40.], Label::new("tool"));
+            for (tool, text) in [
+                (ToolState::Freehand, "Freehand"),
+                (ToolState::Line, "Line"),
+                (ToolState::Rectangle, "Rectangle"),
+                (ToolState::FilledRectangle, "Filled rectangle"),
+                (ToolState::FloodFill, "Flood fill"),
+                (ToolState::RoadPath, "Road path"),
+            ] {
+                add_state_radio_button(&mut set_tool_state, &tool_state, tool, text, ui);
+            }
+
+            ui.add_sized([LEFT_BAR_WIDTH_PX - 20., 40.], Label::new("transform"));
+            if let Some(transform) = add_transform_buttons(&mut shift_by, ui) {
+                transform_ev.write(EditorTransformBoardMessage(transform));
+            }
+
+            if let Some(err_text) = &editor.err_text {
+                ui.add_space(20.);
+                add_error_box(err_text, ui);
+            }
+
+            // Validation errors, their tiles are marked on the board
+            if !editor.errors.is_empty() {
+                ui.add_space(20.);
+                ScrollArea::vertical().show(ui, |ui| {
+                    for err in editor.errors.iter() {
+                        add_error_box(&err.to_string(), ui);
+                    }
+                });
+            }
         });
 }
 
-fn add_tile_radio_button(
-    set_current_state: &mut NextState<SettileState>,
-    current_state: &State<SettileState>,
-    state: SettileState,
+pub(super) struct ShiftBy(i32);
+
+impl Default for ShiftBy {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+fn add_transform_buttons(shift_by: &mut ShiftBy, ui: &mut egui::Ui) -> Option<BoardTransform> {
+    let mut transform = None;
+    let button_width = LEFT_BAR_WIDTH_PX - 20.;
+    for (text, board_transform) in [
+        ("Rotate 90°", BoardTransform::RotateClockwise),
+        ("Mirror ↔", BoardTransform::MirrorHorizontally),
+        ("Mirror ↕", BoardTransform::MirrorVertically),
+    ] {
+        if ui
+            .add_sized([button_width, 24.], Button::new(text))
+            .clicked()
+        {
+            transform = Some(board_transform);
+        }
+    }
+    ui.horizontal(|ui| {
+        ui.label("Shift by");
+        ui.add(DragValue::new(&mut shift_by.0).range(1..=32));
+    });
+    ui.horizontal(|ui| {
+        for (text, dir) in [
+            ("←", IVec2::NEG_X),
+            ("→", IVec2::X),
+            ("↑", IVec2::Y),
+            ("↓", IVec2::NEG_Y),
+        ] {
+            if ui.add_sized([24., 24.], Button::new(text)).clicked() {
+                transform = Some(BoardTransform::Shift(dir * shift_by.0));
+            }
+        }
+    });
+    transform
+}
+
+fn add_state_radio_button<S: FreelyMutableState>(
+    set_current_state: &mut NextState<S>,
+    current_state: &State<S>,
+    state: S,
     text: &str,
     ui: &mut egui::Ui,
 ) {
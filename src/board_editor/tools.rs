@@ -0,0 +1,176 @@
+use crate::board::{Board, Tile};
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[derive(States, Clone, Copy, Eq, PartialEq, Debug, Hash, Default)]
+pub(super) enum ToolState {
+    #[default]
+    Freehand,
+    Line,
+    Rectangle,
+    FilledRectangle,
+    FloodFill,
+    RoadPath,
+}
+
+impl ToolState {
+    // Shapes are spanned from the pressed to the released tile
+    pub fn is_shape(&self) -> bool {
+        matches!(
+            self,
+            ToolState::Line
+                | ToolState::Rectangle
+                | ToolState::FilledRectangle
+                | ToolState::RoadPath
+        )
+    }
+
+    pub fn tiles(&self, board: &Board, start: UVec2, end: UVec2) -> Vec<UVec2> {
+        match self {
+            ToolState::Freehand => vec![end],
+            ToolState::Line => line_tiles(start, end),
+            ToolState::Rectangle => rect_tiles(start, end, false),
+            ToolState::FilledRectangle => rect_tiles(start, end, true),
+            ToolState::FloodFill => flood_fill_tiles(board, end),
+            ToolState::RoadPath => road_path_tiles(board, start, end),
+        }
+    }
+}
+
+// Currently dragged shape
+#[derive(Resource)]
+pub(super) struct ShapeDrag {
+    pub start: UVec2,
+    pub end: UVec2,
+    pub tile: Tile,
+}
+
+fn neighbors(board: &Board, pos: UVec2) -> impl Iterator<Item = UVec2> + '_ {
+    [(1, 0), (0, 1), (-1, 0), (0, -1)]
+        .into_iter()
+        .filter_map(move |(x, y)| pos.as_ivec2().checked_add(IVec2::new(x, y)))
+        .filter(|pos| pos.x >= 0 && pos.y >= 0)
+        .map(|pos| pos.as_uvec2())
+        .filter(|pos| board.get_tile(pos).is_some())
+}
+
+// Bresenham line, diagonal steps get an extra tile, so drawn roads stay connected
+fn line_tiles(start: UVec2, end: UVec2) -> Vec<UVec2> {
+    let (start, end) = (start.as_ivec2(), end.as_ivec2());
+    let delta = IVec2::new((end.x - start.x).abs(), -(end.y - start.y).abs());
+    let step = (end - start).signum();
+    let mut pos = start;
+    let mut err = delta.x + delta.y;
+    let mut tiles = vec![pos.as_uvec2()];
+    while pos != end {
+        let err2 = 2 * err;
+        if err2 >= delta.y {
+            err += delta.y;
+            pos.x += step.x;
+            tiles.push(pos.as_uvec2());
+        }
+        if err2 <= delta.x {
+            err += delta.x;
+            pos.y += step.y;
+            tiles.push(pos.as_uvec2());
+        }
+    }
+    tiles
+}
+
+fn rect_tiles(start: UVec2, end: UVec2, is_filled: bool) -> Vec<UVec2> {
+    let (min, max) = (start.min(end), start.max(end));
+    (min.y..=max.y)
+        .flat_map(|y| (min.x..=max.x).map(move |x| UVec2::new(x, y)))
+        .filter(|pos| {
+            is_filled || pos.x == min.x || pos.x == max.x || pos.y == min.y || pos.y == max.y
+        })
+        .collect()
+}
+
+// All tiles of the same kind connected to the clicked one
+fn flood_fill_tiles(board: &Board, pos: UVec2) -> Vec<UVec2> {
+    let Some(tile) = board.get_tile(&pos).copied() else {
+        return Vec::new();
+    };
+    let mut tiles = vec![pos];
+    let mut visited = HashSet::from([pos]);
+    let mut i = 0;
+    while let Some(pos) = tiles.get(i).copied() {
+        for neighbor in neighbors(board, pos) {
+            if board.get_tile(&neighbor) == Some(&tile) && visited.insert(neighbor) {
+                tiles.push(neighbor);
+            }
+        }
+        i += 1;
+    }
+    tiles
+}
+
+// Shortest orthogonal way which doesn't touch other roads between its ends,
+// so the new road doesn't get accidental branches
+fn road_path_tiles(board: &Board, start: UVec2, end: UVec2) -> Vec<UVec2> {
+    let is_free = |pos: UVec2| {
+        pos == end
+            || neighbors(board, pos).all(|neighbor| {
+                neighbor == start
+                    || neighbor == end
+                    || !board.get_tile(&neighbor).is_some_and(Tile::is_road)
+            })
+    };
+    let mut came_from: HashMap<UVec2, UVec2> = HashMap::from([(start, start)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(pos) = queue.pop_front() {
+        if pos == end {
+            let mut path = vec![end];
+            let mut pos = end;
+            while pos != start {
+                pos = came_from[&pos];
+                path.push(pos);
+            }
+            path.reverse();
+            return path;
+        }
+        for neighbor in neighbors(board, pos) {
+            if !came_from.contains_key(&neighbor) && is_free(neighbor) {
+                came_from.insert(neighbor, pos);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    // Blocked by other roads, draw the plain corner way
+    let corner = UVec2::new(end.x, start.y);
+    let mut tiles = rect_tiles(start, corner, true);
+    tiles.extend(rect_tiles(corner, end, true).into_iter().skip(1));
+    tiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_tiles_are_connected() {
+        let tiles = line_tiles(UVec2::new(0, 0), UVec2::new(5, 2));
+        assert_eq!(tiles.first(), Some(&UVec2::new(0, 0)));
+        assert_eq!(tiles.last(), Some(&UVec2::new(5, 2)));
+        assert_eq!(tiles.len(), 8);
+        assert!(tiles
+            .windows(2)
+            .all(|pair| pair[0].as_ivec2().distance_squared(pair[1].as_ivec2()) == 1));
+    }
+
+    #[test]
+    fn test_road_path_avoids_other_roads() {
+        let mut board = Board::empty(5, 3);
+        *board.get_tile_mut(&UVec2::new(2, 0)).unwrap() = Tile::Road;
+        let tiles = road_path_tiles(&board, UVec2::new(0, 0), UVec2::new(4, 0));
+        assert_eq!(tiles.first(), Some(&UVec2::new(0, 0)));
+        assert_eq!(tiles.last(), Some(&UVec2::new(4, 0)));
+        assert!(!tiles.contains(&UVec2::new(1, 0)));
+        assert!(tiles.contains(&UVec2::new(2, 2)));
+        assert!(tiles
+            .windows(2)
+            .all(|pair| pair[0].as_ivec2().distance_squared(pair[1].as_ivec2()) == 1));
+    }
+}
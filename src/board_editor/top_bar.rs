@@ -1,21 +1,31 @@
 use super::{
-    actions::EditorLeaveMessage,
-    popups::{EditBoardWindow, LoadBoardWindow, NewBoardWindow, Popups, SaveBoardWindow},
-    BoardEditor, LEFT_BAR_WIDTH_PX, TOP_BAR_HEIGHT_PX,
+    actions::{EditorLeaveMessage, EditorPasteBoardMessage, EditorRedoMessage, EditorUndoMessage},
+    history::EditorHistory,
+    popups::{
+        EditBoardWindow, GenerateBoardWindow, LoadBoardWindow, NewBoardWindow, Popups,
+        SaveBoardWindow, TestPlayWindow,
+    },
+    EditorMapMeta, TOP_BAR_HEIGHT_PX,
 };
-use crate::{board::Board, utils::add_error_box};
+use crate::board::{map_file::board_to_ascii, Board};
 use bevy::prelude::*;
 use bevy_egui::{
     egui::{self, Response, TopBottomPanel, Ui},
-    EguiContexts,
+    EguiClipboard, EguiContexts,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn add_top_menu_bar(
     mut egui_ctx: EguiContexts,
     mut popup: ResMut<Popups>,
     mut leave_ev: MessageWriter<EditorLeaveMessage>,
-    state: Res<BoardEditor>,
+    mut undo_ev: MessageWriter<EditorUndoMessage>,
+    mut redo_ev: MessageWriter<EditorRedoMessage>,
+    mut paste_ev: MessageWriter<EditorPasteBoardMessage>,
+    mut clipboard: ResMut<EguiClipboard>,
+    history: Res<EditorHistory>,
     board: Res<Board>,
+    meta: Res<EditorMapMeta>,
 ) {
     let Ok(ctx) = egui_ctx.ctx_mut() else { return };
     TopBottomPanel::top("map_editor_top_bar").show(ctx, |ui| {
@@ -55,9 +65,39 @@ pub(super) fn add_top_menu_bar(
                 }
             }
 
-            if let Some(err_text) = &state.err_text {
-                ui.add_space(LEFT_BAR_WIDTH_PX - 80.);
-                add_error_box(err_text, ui);
+            // Ascii form, to share maps as plain text
+            if add_top_bar_button("Copy", ui).clicked() {
+                clipboard.set_text(&board_to_ascii(&board, &meta));
+            }
+
+            if add_top_bar_button("Paste", ui).clicked() {
+                if let Some(text) = clipboard.get_text() {
+                    paste_ev.write(EditorPasteBoardMessage(text));
+                }
+            }
+
+            if add_top_bar_button("Generate", ui).clicked() {
+                *popup = match *popup {
+                    Popups::Generate(_) => Popups::None,
+                    _ => Popups::Generate(GenerateBoardWindow::default()),
+                }
+            }
+
+            if add_top_bar_button("Test play", ui).clicked() {
+                *popup = match *popup {
+                    Popups::TestPlay(_) => Popups::None,
+                    _ => Popups::TestPlay(TestPlayWindow::default()),
+                }
+            }
+
+            ui.add_space(20.);
+
+            if add_top_bar_button_enabled("Undo", history.can_undo(), ui).clicked() {
+                undo_ev.write(EditorUndoMessage);
+            }
+
+            if add_top_bar_button_enabled("Redo", history.can_redo(), ui).clicked() {
+                redo_ev.write(EditorRedoMessage);
             }
         });
     });
@@ -69,3 +109,8 @@ fn add_top_bar_button(text: &str, ui: &mut Ui) -> Response {
         egui::Button::new(text).frame(false),
     )
 }
+
+fn add_top_bar_button_enabled(text: &str, is_enabled: bool, ui: &mut Ui) -> Response {
+    ui.add_enabled_ui(is_enabled, |ui| add_top_bar_button(text, ui))
+        .inner
+}
@@ -1,39 +1,62 @@
 use std::time::Duration;
 
 use self::{
-    actions::{build_menu::BuildMenuCloseMessage, GameActions},
+    actions::{
+        build_menu::BuildMenuCloseMessage, resources::on_resource_animations, GameActions, Labels,
+    },
     build_menus::{draw_build_menu, BuildMenu, BuildMenuScreen},
     controls::{keyboard_input, mouse_input},
-    statistics::{EnemyKillCount, LaserShotsFired, RocketsFired},
+    enemies::definitions::EnemyDefinitions,
+    replay::{
+        play_commands, play_game_actions, record_commands, record_game_actions,
+        start_replay_recording, write_replay, write_replay_on_window_close, ReplayPlayback,
+        ReplayRecorder,
+    },
+    save::{
+        delete_save_on_game_over, on_save_game, restore_save_game, save_on_window_close,
+        SaveGameMessage,
+    },
+    statistics::{EnemyKillCount, GrenadesFired, LaserShotsFired, RocketsFired, TowerUpgrades},
     systems::{
         game_over::GameOverTimer,
+        sell_menu::SellMenu,
+        tower_panel::TowerPanel,
         wave::{Wave, WaveState},
         GameSystems,
     },
+    test_play::{fill_test_play_resources, start_test_play, TestPlay},
 };
 use crate::{
     balance::{GAME_OVER_COUNTDOWN_SECS, INITIAL_WAVE_DELAY_SECS, START_ENERGY, START_MATERIALS},
     board::{visualisation::BoardVisualisation, Board, BoardCache, Tile},
     utils::{
-        collision::Collisions, despawn_all_of, zoom_cam_to_board, Difficulty, Energy, IngameTime,
-        IngameTimestamp, Materials, Vec2Board,
+        collision::Collisions,
+        despawn_all_of,
+        interpolation::{begin_tick_translation_system, interpolation_system},
+        zoom_cam_to_board, Difficulty, Energy, GameRng, IngameTime, IngameTimestamp, Materials,
+        Seed, Vec2Board,
     },
     CamMutQuery, GameState,
 };
 use bevy::{prelude::*, window::WindowResized};
+use bevy_egui::input::egui_wants_any_pointer_input;
+use serde::{Deserialize, Serialize};
 
 mod actions;
 mod build_menus;
 mod controls;
 mod enemies;
-mod statistics;
+pub(crate) mod replay;
+pub(crate) mod save;
+pub(crate) mod statistics;
 mod systems;
+pub(crate) mod test_play;
 
 type BoardVisu = BoardVisualisation<GameScreen>;
 type BaseLevel = u8;
 
 #[derive(States, Component, Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
-enum IngameState {
+pub(crate) enum IngameState {
     #[default]
     None,
     Running,
@@ -45,36 +68,140 @@ enum IngameState {
 struct HoveredTile(Option<(Vec2Board, Tile)>);
 
 pub const GAME_OVER_COUNTDOWN_TIME: Duration = Duration::from_secs(GAME_OVER_COUNTDOWN_SECS);
+
+// Gameplay advances in fixed ticks, game speed only changes how many ticks run per frame
+pub const SIMULATION_TICK: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+// Windowed game: simulation plus input handling, camera and board drawing
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.init_state::<IngameState>()
-            .add_plugins((GameSystems, GameActions))
-            .add_systems(OnEnter(GameState::Game), game_setup)
+        app.add_plugins(GameSimulation)
+            .add_systems(OnEnter(GameState::Game), draw_game.after(restore_save_game))
+            .add_systems(
+                OnEnter(GameState::Game),
+                start_test_play
+                    .before(game_setup)
+                    .run_if(resource_exists::<TestPlay>),
+            )
+            .add_systems(
+                OnEnter(GameState::Game),
+                start_replay_recording
+                    .after(game_setup)
+                    .before(restore_save_game),
+            )
             .add_systems(
                 Update,
-                (keyboard_input, on_resize).run_if(in_state(GameState::Game)),
+                (
+                    keyboard_input,
+                    on_resize,
+                    on_resource_animations,
+                    interpolation_system,
+                )
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(
+                Update,
+                mouse_input.run_if(
+                    in_state(IngameState::Running)
+                        .and(not(resource_exists::<ReplayPlayback>))
+                        .and(not(egui_wants_any_pointer_input)),
+                ),
+            )
+            .add_systems(
+                Update,
+                save_on_window_close.run_if(
+                    (in_state(IngameState::Running).or(in_state(IngameState::Pause)))
+                        .and(not(resource_exists::<TestPlay>)),
+                ),
+            )
+            .add_systems(
+                OnEnter(IngameState::GameOver),
+                delete_save_on_game_over.run_if(
+                    not(resource_exists::<TestPlay>).and(not(resource_exists::<ReplayPlayback>)),
+                ),
+            )
+            .add_systems(
+                FixedUpdate,
+                fill_test_play_resources
+                    .in_set(Labels::Input)
+                    .run_if(is_simulation_running.and(resource_exists::<TestPlay>)),
             )
             .add_systems(
                 Update,
-                (tick_ingame_timer, mouse_input).run_if(in_state(IngameState::Running)),
+                (record_game_actions, write_replay_on_window_close)
+                    .run_if(resource_exists::<ReplayRecorder>),
+            )
+            .add_systems(
+                FixedUpdate,
+                record_commands
+                    .in_set(Labels::Input)
+                    .run_if(resource_exists::<ReplayRecorder>),
             )
             .add_systems(
                 OnExit(GameState::Game),
                 (
-                    clean_up_game,
-                    despawn_all_of::<GameScreen>,
                     despawn_all_of::<BuildMenuScreen>,
+                    write_replay.before(clean_up_game),
                 ),
             );
     }
 }
 
+// Gameplay only, runs without window, camera, egui or asset server
+pub struct GameSimulation;
+
+impl Plugin for GameSimulation {
+    fn build(&self, app: &mut App) {
+        app.init_state::<IngameState>()
+            .insert_resource(Time::<Fixed>::from_duration(SIMULATION_TICK))
+            .init_resource::<EnemyDefinitions>()
+            .add_message::<SaveGameMessage>()
+            .add_plugins((GameSystems, GameActions))
+            .add_systems(
+                OnEnter(GameState::Game),
+                (game_setup, restore_save_game).chain(),
+            )
+            .add_systems(
+                Update,
+                on_save_game
+                    .run_if(in_state(IngameState::Running).or(in_state(IngameState::Pause))),
+            )
+            .add_systems(Update, set_game_speed.run_if(in_state(GameState::Game)))
+            .add_systems(
+                FixedFirst,
+                begin_tick_translation_system.run_if(is_simulation_running),
+            )
+            .add_systems(
+                FixedUpdate,
+                tick_ingame_timer
+                    .before(Labels::Input)
+                    .run_if(is_simulation_running),
+            )
+            .add_systems(
+                FixedUpdate,
+                play_commands
+                    .in_set(Labels::Input)
+                    .run_if(is_simulation_running.and(resource_exists::<ReplayPlayback>)),
+            )
+            .add_systems(
+                Update,
+                play_game_actions
+                    .run_if(in_state(GameState::Game).and(resource_exists::<ReplayPlayback>)),
+            )
+            .add_systems(
+                OnExit(GameState::Game),
+                (clean_up_game, despawn_all_of::<GameScreen>),
+            );
+    }
+}
+
 #[allow(dead_code)]
-#[derive(Resource, Clone)]
+#[derive(Resource, Clone, Serialize, Deserialize)]
 pub(crate) struct Game {
     difficulty: Difficulty,
+    seed: Seed,
     energy: Energy,
     materials: Materials,
     wave_no: u32,
@@ -85,9 +212,10 @@ pub(crate) struct Game {
 }
 
 impl Game {
-    pub fn new(difficulty: Difficulty) -> Self {
+    pub fn new(difficulty: Difficulty, seed: Seed) -> Self {
         Self {
             difficulty,
+            seed,
             energy: START_ENERGY,
             materials: START_MATERIALS,
             wave_no: 0,
@@ -97,6 +225,31 @@ impl Game {
             speed: 1.,
         }
     }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    pub fn is_between_waves(&self) -> bool {
+        self.next_wave_spawn.is_some()
+    }
+
+    pub fn seed(&self) -> Seed {
+        self.seed
+    }
+
+    pub fn wave_no(&self) -> u32 {
+        self.wave_no
+    }
+
+    pub fn energy(&self) -> Energy {
+        self.energy
+    }
+
+    pub fn materials(&self) -> Materials {
+        self.materials
+    }
 }
 
 // Tag component used to tag entities added on the game screen
@@ -114,56 +267,97 @@ fn on_resize(
     }
 }
 
-#[allow(clippy::too_many_arguments)]
 fn game_setup(
     mut cmds: Commands,
     mut set_ingame_state: ResMut<NextState<IngameState>>,
+    game: Res<Game>,
+) {
+    cmds.insert_resource(BoardVisu::new(1.));
+    cmds.insert_resource(GameRng::new(game.seed));
+    cmds.init_resource::<IngameTime>();
+    cmds.init_resource::<BuildMenu>();
+    cmds.init_resource::<TowerPanel>();
+    cmds.init_resource::<SellMenu>();
+    cmds.init_resource::<Collisions>();
+    cmds.init_resource::<GameOverTimer>();
+    cmds.init_resource::<HoveredTile>();
+    cmds.init_resource::<EnemyKillCount>();
+    cmds.init_resource::<LaserShotsFired>();
+    cmds.init_resource::<RocketsFired>();
+    cmds.init_resource::<GrenadesFired>();
+    cmds.init_resource::<TowerUpgrades>();
+
+    set_ingame_state.set(IngameState::Running);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_game(
+    mut cmds: Commands,
     mut q_cam: CamMutQuery,
     bm_close_ev: MessageWriter<BuildMenuCloseMessage>,
     q_win: Query<&Window>,
     board: Res<Board>,
     board_cache: Res<BoardCache>,
+    visu: Res<BoardVisu>,
     game: Res<Game>,
     assets: Res<AssetServer>,
 ) {
     zoom_cam_to_board(&board, &mut q_cam, q_win);
-    let visu = BoardVisu::new(1.);
     visu.draw_board(&mut cmds, &board, &board_cache, &assets);
     draw_build_menu(&mut cmds, bm_close_ev, game.base_lvl, &assets);
+}
 
-    cmds.insert_resource(visu);
-    cmds.init_resource::<IngameTime>();
-    cmds.init_resource::<BuildMenu>();
-    cmds.init_resource::<Collisions>();
-    cmds.init_resource::<GameOverTimer>();
-    cmds.init_resource::<HoveredTile>();
-    cmds.init_resource::<EnemyKillCount>();
-    cmds.init_resource::<LaserShotsFired>();
-    cmds.init_resource::<RocketsFired>();
+fn tick_ingame_timer(mut timer: ResMut<IngameTime>, time: Res<Time<Fixed>>) {
+    timer.tick(time.delta());
+}
 
-    set_ingame_state.set(IngameState::Running);
+// Virtual time feeds the fixed ticks, so scaling it runs more ticks per frame
+fn set_game_speed(mut time: ResMut<Time<Virtual>>, game: Res<Game>) {
+    if time.relative_speed() != game.speed {
+        time.set_relative_speed(game.speed);
+    }
 }
 
-fn tick_ingame_timer(mut timer: ResMut<IngameTime>, time: Res<Time>, game: Res<Game>) {
-    timer.tick(Duration::from_secs_f32(time.delta_secs() * game.speed));
+// Run condition of the fixed ticks. States change once per frame, so a game over
+// must stop the ticks left in the frame before the state does.
+fn is_simulation_running(
+    ingame_state: Option<Res<State<IngameState>>>,
+    go_timer: Option<Res<GameOverTimer>>,
+    time: Option<Res<IngameTime>>,
+) -> bool {
+    let is_running = ingame_state.is_some_and(|state| *state.get() == IngameState::Running);
+    let is_game_over = match (go_timer, time) {
+        (Some(go_timer), Some(time)) => go_timer.is_over(time.now()),
+        _ => false,
+    };
+    is_running && !is_game_over
 }
 
 fn clean_up_game(
     mut cmds: Commands,
+    mut time: ResMut<Time<Virtual>>,
     mut set_wave_state: ResMut<NextState<WaveState>>,
     mut set_ingame_state: ResMut<NextState<IngameState>>,
 ) {
     set_wave_state.set(WaveState::None);
     set_ingame_state.set(IngameState::None);
+    time.set_relative_speed(1.);
     cmds.remove_resource::<Game>();
     cmds.remove_resource::<Board>();
     cmds.remove_resource::<BoardCache>();
     cmds.remove_resource::<BoardVisu>();
     cmds.remove_resource::<Wave>();
     cmds.remove_resource::<IngameTime>();
+    cmds.remove_resource::<GameRng>();
     cmds.remove_resource::<BuildMenu>();
+    cmds.remove_resource::<TowerPanel>();
+    cmds.remove_resource::<SellMenu>();
     cmds.remove_resource::<GameOverTimer>();
     cmds.remove_resource::<EnemyKillCount>();
     cmds.remove_resource::<LaserShotsFired>();
     cmds.remove_resource::<RocketsFired>();
+    cmds.remove_resource::<GrenadesFired>();
+    cmds.remove_resource::<TowerUpgrades>();
+    cmds.remove_resource::<ReplayRecorder>();
+    cmds.remove_resource::<ReplayPlayback>();
 }
@@ -3,8 +3,10 @@ use crate::{
     utils::{towers::TowerRangeCircle, visible, GameState},
 };
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use self::{
+    build::BuildMessage,
     build_menu::{
         BuildMenuBuildMessage, BuildMenuCloseMessage, BuildMenuHideMessage, BuildMenuOpenMessage,
         BuildMenuScrollMessage,
@@ -16,20 +18,26 @@ use self::{
     damage::{on_damage, DamageMessage},
     explosions::{on_explosions, ExplosionMessage},
     resources::{on_change_resources, ResourcesMessage},
+    sell::{on_sell, SellMessage},
+    status_effects::{on_status_effect, StatusEffectMessage},
     tile::{on_tile_actions, TileActionsMessage},
-    tower::{on_tower_actions, TowerActionsMessage},
+    tower::{on_tower_actions, on_tower_commands, TowerActionsMessage, TowerCommandMessage},
     wave::{on_wave_actions, WaveActionsMessage},
 };
 
 use super::{
-    build_menus::BuildMenuScreen, systems::wave::WaveState, Game, GameScreen, IngameState,
+    build_menus::BuildMenuScreen, is_simulation_running, systems::wave::WaveState,
+    test_play::TestPlay, Game, GameScreen, IngameState,
 };
 
+pub(super) mod build;
 pub(super) mod build_menu;
 pub(super) mod collision;
 pub(super) mod damage;
 pub(super) mod explosions;
 pub(super) mod resources;
+pub(super) mod sell;
+pub(super) mod status_effects;
 pub(super) mod tile;
 pub(super) mod tower;
 pub(super) mod wave;
@@ -43,7 +51,7 @@ type RangeCircleQuery<'w, 's, 'a> = Query<
 
 type GameScreenQuery<'w, 's> = Query<'w, 's, Entity, With<GameScreen>>;
 
-#[derive(Message)]
+#[derive(Message, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GameActionMessage {
     BackToMainMenu,
     ActivateOverview,
@@ -57,9 +65,9 @@ pub enum GameActionMessage {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
 pub enum Labels {
+    Input,
+    Systems,
     Actions,
-    CollisionAdd,
-    CollisionRemove,
 }
 
 pub struct GameActions;
@@ -71,8 +79,10 @@ impl Plugin for GameActions {
             .add_message::<ResourcesMessage>()
             .add_message::<TileActionsMessage>()
             .add_message::<TowerActionsMessage>()
+            .add_message::<TowerCommandMessage>()
             .add_message::<DamageMessage>()
             .add_message::<ExplosionMessage>()
+            .add_message::<StatusEffectMessage>()
             .add_message::<EnemyCollisionAddMessage>()
             .add_message::<EnemyCollisionRemoveMessage>()
             .add_message::<BuildMenuScrollMessage>()
@@ -80,13 +90,12 @@ impl Plugin for GameActions {
             .add_message::<BuildMenuCloseMessage>()
             .add_message::<BuildMenuHideMessage>()
             .add_message::<BuildMenuBuildMessage>()
+            .add_message::<BuildMessage>()
+            .add_message::<SellMessage>()
+            .configure_sets(FixedUpdate, Labels::Input.before(Labels::Systems))
             .add_systems(
                 Update,
                 (
-                    on_change_resources,
-                    on_damage,
-                    on_explosions,
-                    on_tower_actions,
                     on_game_actions,
                     on_tile_actions,
                     build_menu::on_open,
@@ -98,22 +107,23 @@ impl Plugin for GameActions {
                     .run_if(in_state(GameState::Game)),
             )
             .add_systems(
-                Update,
-                (/*on_game_actions,*/on_wave_actions)
+                FixedUpdate,
+                (
+                    on_wave_actions,
+                    on_enemy_collision_add,
+                    on_enemy_collision_remove,
+                    on_tower_actions,
+                    on_status_effect,
+                    on_damage,
+                    on_explosions,
+                    build::on_build,
+                    on_tower_commands,
+                    on_sell,
+                    on_change_resources,
+                )
+                    .chain()
                     .in_set(Labels::Actions)
-                    .run_if(in_state(GameState::Game).and(in_state(IngameState::Running))),
-            )
-            .add_systems(
-                Update,
-                on_enemy_collision_add
-                    .in_set(Labels::CollisionAdd)
-                    .run_if(in_state(GameState::Game)),
-            )
-            .add_systems(
-                Update,
-                on_enemy_collision_remove
-                    .in_set(Labels::CollisionRemove)
-                    .run_if(in_state(GameState::Game)),
+                    .run_if(is_simulation_running),
             );
     }
 }
@@ -129,6 +139,7 @@ fn on_game_actions(
     mut q_range_circle: RangeCircleQuery,
     ingame_state: Res<State<IngameState>>,
     mut set_ingame_state: ResMut<NextState<IngameState>>,
+    test_play: Option<Res<TestPlay>>,
 ) {
     if !game_actions.is_empty() {
         for event in game_actions.read() {
@@ -137,6 +148,7 @@ fn on_game_actions(
                 BackToMainMenu => back_to_main_menu(
                     &mut cmds,
                     &mut set_game_state,
+                    TestPlay::exit_state(test_play.as_deref()),
                     &mut set_ingame_state,
                     &mut set_wave_state,
                     &mut q_game_screen,
@@ -183,6 +195,7 @@ fn set_range_circles(query: &mut RangeCircleQuery, is_visible: bool) {
 fn back_to_main_menu(
     cmds: &mut Commands,
     set_game_state: &mut NextState<GameState>,
+    to: GameState,
     set_ingame_state: &mut NextState<IngameState>,
     set_wave_state: &mut NextState<WaveState>,
     query: &mut GameScreenQuery,
@@ -192,5 +205,5 @@ fn back_to_main_menu(
     }
     set_wave_state.set(WaveState::None);
     set_ingame_state.set(IngameState::None);
-    set_game_state.set(GameState::Menu);
+    set_game_state.set(to);
 }
@@ -0,0 +1,84 @@
+use super::{
+    build_menu::{building_build_cost, negate_cost, tower_build_cost},
+    resources::{consume, ResourcesMessage},
+};
+use crate::{
+    board::{visualisation::TILE_SIZE, Board, Tile},
+    game::GameScreen,
+    utils::{
+        buildings::{
+            factory::{spawn_factory, Factory},
+            power_plant::{spawn_power_plant, PowerPlant},
+            Building,
+        },
+        towers::{draw_tower, Tower, Towerless},
+        BoardPos, IngameTime, Vec2Board,
+    },
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BuildItem {
+    Tower(Towerless),
+    Building(Building),
+}
+
+// Places a tower or building on the board, written by the build menu or a replay
+#[derive(Message, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BuildMessage {
+    pub tile_pos: UVec2,
+    pub item: BuildItem,
+}
+
+pub(super) fn on_build(
+    mut evr: MessageReader<BuildMessage>,
+    mut cmds: Commands,
+    mut res_actions: MessageWriter<ResourcesMessage>,
+    q_occupied: Query<&BoardPos, With<GameScreen>>,
+    board: Res<Board>,
+    time: Res<IngameTime>,
+) {
+    let built_at = time.now();
+    for ev in evr.read() {
+        let is_occupied = q_occupied.iter().any(|pos| **pos == ev.tile_pos);
+        let pos = Vec2Board::from_uvec2_middle(&ev.tile_pos);
+        match (board.get_tile(&ev.tile_pos), ev.item) {
+            _ if is_occupied => (),
+            (Some(tile), BuildItem::Tower(kind)) if tile.is_tower_ground() => {
+                if let Some(mut tower) = Tower::new(kind, pos) {
+                    tower.values_mut().scale_range(tile.range_factor());
+                    tower.values_mut().built_at = built_at;
+                    draw_tower::<GameScreen>(&mut cmds, pos, &tower);
+                    consume(&mut res_actions, negate_cost(tower_build_cost(&tower)), pos);
+                }
+            }
+            (Some(Tile::BuildingGround), BuildItem::Building(building)) => {
+                match building {
+                    Building::PowerPlant => spawn_power_plant::<GameScreen>(
+                        &mut cmds,
+                        PowerPlant {
+                            built_at,
+                            ..PowerPlant::new(pos)
+                        },
+                        TILE_SIZE,
+                    ),
+                    Building::Factory => spawn_factory::<GameScreen>(
+                        &mut cmds,
+                        Factory {
+                            built_at,
+                            ..Factory::new(pos)
+                        },
+                        TILE_SIZE,
+                    ),
+                }
+                consume(
+                    &mut res_actions,
+                    negate_cost(building_build_cost(&building)),
+                    pos,
+                );
+            }
+            _ => (),
+        }
+    }
+}
@@ -1,31 +1,26 @@
 use crate::{
     balance::{
-        FACTORY_ENERGY_COST, FACTORY_MATERIALS_COST, LASER_TOWER_ENERGY_COST,
-        LASER_TOWER_MATERIALS_COST, POWER_PLANT_ENERGY_COST, POWER_PLANT_MATERIALS_COST,
-        ROCKET_TOWER_ENERGY_COST, ROCKET_TOWER_MATERIALS_COST,
+        FACTORY_ENERGY_COST, FACTORY_MATERIALS_COST, GRENADE_TOWER_ENERGY_COST,
+        GRENADE_TOWER_MATERIALS_COST, LASER_TOWER_ENERGY_COST, LASER_TOWER_MATERIALS_COST,
+        MICROWAVE_TOWER_ENERGY_COST, MICROWAVE_TOWER_MATERIALS_COST, POWER_PLANT_ENERGY_COST,
+        POWER_PLANT_MATERIALS_COST, ROCKET_TOWER_ENERGY_COST, ROCKET_TOWER_MATERIALS_COST,
+        TOWER_UPGRADE_COST_FACTOR,
     },
     board::{visualisation::TILE_SIZE, Board, Tile},
-    game::{
-        build_menus::{
-            BuildMenu, BuildMenuCircle, BuildMenuCostPanel, BuildMenuEnergyCostText,
-            BuildMenuMaterialsCostText, BuildMenuScreen,
-        },
-        GameScreen,
+    game::build_menus::{
+        BuildMenu, BuildMenuCircle, BuildMenuCostPanel, BuildMenuEnergyCostText,
+        BuildMenuMaterialsCostText, BuildMenuScreen,
     },
     utils::{
-        buildings::{
-            factory::{spawn_factory, Factory},
-            power_plant::{spawn_power_plant, PowerPlant},
-            Building, BuildingBase,
-        },
-        towers::{draw_tower, ChildOfTower, Tower, TowerRangeCircle},
+        buildings::{Building, BuildingBase},
+        towers::{ChildOfTower, Tower, TowerRangeCircle},
         Energy, Materials, Vec2Board,
     },
 };
 use bevy::ecs::query::QueryFilter;
 use bevy::prelude::*;
 
-use super::resources::{consume, ResourcesMessage};
+use super::build::{BuildItem, BuildMessage};
 
 const BUILD_COST_TEXT_Y_OFFSET: f32 = -0.58;
 
@@ -103,6 +98,7 @@ pub enum BuildMenuScrollMessage {
     After = 1,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn on_scroll(
     mut evr: MessageReader<BuildMenuScrollMessage>,
     mut tbm: ResMut<BuildMenu>,
@@ -132,6 +128,7 @@ pub(super) fn on_scroll(
 #[derive(Message)]
 pub struct BuildMenuOpenMessage(pub UVec2);
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn on_open(
     mut evr: MessageReader<BuildMenuOpenMessage>,
     mut tbm: ResMut<BuildMenu>,
@@ -203,6 +200,7 @@ fn set_build_circle(q_tmc: &mut QueryBuildMenuCircle, translation: Vec3) {
     circle.1.translation = translation;
 }
 
+#[allow(clippy::too_many_arguments)]
 fn show_preview(
     tm: &mut BuildMenu,
     q_tower: &mut QueryTowerMenuParents,
@@ -217,7 +215,7 @@ fn show_preview(
     hide_building_preview_base(q_building);
 
     match *tile {
-        Tile::TowerGround => show_build_costs(
+        tile if tile.is_tower_ground() => show_build_costs(
             q_cost_panel,
             q_energy_cost,
             q_materials_cost,
@@ -285,6 +283,7 @@ fn hide_building_preview_base(q_building: &mut QueryBuildingMenuParents) {
     });
 }
 
+#[allow(clippy::too_many_arguments)]
 fn scroll(
     tm: &mut BuildMenu,
     q_tower: &mut QueryTowerMenuParents,
@@ -297,7 +296,7 @@ fn scroll(
 ) {
     if let Some(tile) = board.get_tile(&tm.tile_pos) {
         let translation = Vec2Board::from_uvec2_middle(&tm.tile_pos).to_scaled_vec3(3.);
-        if *tile == Tile::TowerGround {
+        if tile.is_tower_ground() {
             let count = q_tower.iter().count();
             let new_i = tm.selected_tower_index as isize + additor;
             if count > 1 {
@@ -336,86 +335,36 @@ fn scroll(
 #[derive(Message)]
 pub struct BuildMenuBuildMessage;
 
-#[allow(clippy::too_many_arguments)]
+// Turns the selected build menu entry into a build of the simulation
 pub(super) fn on_build(
     mut evr: MessageReader<BuildMenuBuildMessage>,
-    mut cmds: Commands,
-    mut board: ResMut<Board>,
-    tbm: ResMut<BuildMenu>,
-    mut res_actions: MessageWriter<ResourcesMessage>,
+    mut build_ev: MessageWriter<BuildMessage>,
     mut bm_close_ev: MessageWriter<BuildMenuCloseMessage>,
-    q_tower: Query<&Tower>,
+    board: Res<Board>,
+    tbm: Res<BuildMenu>,
     q_qmp_tower: QueryTowerMenuParents,
     q_qmp_building: QueryBuildingMenuParents,
 ) {
     for _ in evr.read() {
-        if let Some(tile) = board.get_tile_mut(&tbm.tile_pos) {
-            if !is_tile_occupied_tower(&q_tower, tbm.tile_pos) {
-                match tile {
-                    Tile::TowerGround => {
-                        place_tower(
-                            &mut cmds,
-                            &mut res_actions,
-                            tbm.get_selected_tower(&q_qmp_tower),
-                            &tbm.tile_pos,
-                        );
-                    }
-                    Tile::BuildingGround => {
-                        place_building(
-                            &mut cmds,
-                            &mut res_actions,
-                            tbm.get_selected_building(&q_qmp_building),
-                            &tbm.tile_pos,
-                        );
-                    }
-                    _ => (),
-                };
-            }
+        let item = match board.get_tile(&tbm.tile_pos) {
+            Some(tile) if tile.is_tower_ground() => tbm
+                .get_selected_tower(&q_qmp_tower)
+                .map(|tower| BuildItem::Tower(tower.into())),
+            Some(Tile::BuildingGround) => tbm
+                .get_selected_building(&q_qmp_building)
+                .map(|building| BuildItem::Building(*building)),
+            _ => None,
+        };
+        if let Some(item) = item {
+            build_ev.write(BuildMessage {
+                tile_pos: tbm.tile_pos,
+                item,
+            });
         }
-
         bm_close_ev.write(BuildMenuCloseMessage);
     }
 }
 
-fn is_tile_occupied_tower(query: &Query<&Tower>, tile_pos: UVec2) -> bool {
-    query
-        .iter()
-        .any(|tower| tower.values().pos.as_uvec2() == tile_pos)
-}
-
-fn place_tower(
-    cmds: &mut Commands,
-    res_actions: &mut MessageWriter<ResourcesMessage>,
-    tower: Option<&Tower>,
-    pos: &UVec2,
-) {
-    if let Some(tower) = tower {
-        let pos = Vec2Board::from_uvec2_middle(pos);
-        draw_tower::<GameScreen>(cmds, pos, tower);
-        consume(res_actions, negate_cost(tower_build_cost(tower)), pos);
-    }
-}
-
-fn place_building(
-    cmds: &mut Commands,
-    res_actions: &mut MessageWriter<ResourcesMessage>,
-    building: Option<&Building>,
-    pos: &UVec2,
-) {
-    let pos = Vec2Board::from_uvec2_middle(pos);
-    match building {
-        Some(building @ Building::PowerPlant) => {
-            spawn_power_plant::<GameScreen>(cmds, PowerPlant::new(pos), TILE_SIZE);
-            consume(res_actions, negate_cost(building_build_cost(building)), pos);
-        }
-        Some(building @ Building::Factory) => {
-            spawn_factory::<GameScreen>(cmds, Factory::new(pos), TILE_SIZE);
-            consume(res_actions, negate_cost(building_build_cost(building)), pos);
-        }
-        None => (),
-    }
-}
-
 fn show_build_costs(
     q_cost_panel: &mut QueryBuildMenuCostPanel,
     q_energy_cost: &mut QueryBuildMenuEnergyCostText,
@@ -449,22 +398,33 @@ fn hide_build_costs(q_cost_panel: &mut QueryBuildMenuCostPanel) {
     }
 }
 
-fn tower_build_cost(tower: &Tower) -> (Energy, Materials) {
+pub(super) fn tower_build_cost(tower: &Tower) -> (Energy, Materials) {
     match tower {
         Tower::Laser(_) => (LASER_TOWER_ENERGY_COST, LASER_TOWER_MATERIALS_COST),
-        Tower::Microwave(_) => todo!(),
+        Tower::Microwave(_) => (MICROWAVE_TOWER_ENERGY_COST, MICROWAVE_TOWER_MATERIALS_COST),
         Tower::Rocket(_) => (ROCKET_TOWER_ENERGY_COST, ROCKET_TOWER_MATERIALS_COST),
-        Tower::Grenade(_) => todo!(),
+        Tower::Grenade(_) => (GRENADE_TOWER_ENERGY_COST, GRENADE_TOWER_MATERIALS_COST),
+    }
+}
+
+// Every level costs more than the one before, none past the max level
+pub(in crate::game) fn tower_upgrade_cost(tower: &Tower) -> Option<(Energy, Materials)> {
+    let vals = tower.values();
+    if !vals.can_upgrade() {
+        return None;
     }
+    let (energy, materials) = tower_build_cost(tower);
+    let factor = TOWER_UPGRADE_COST_FACTOR * vals.level as f32;
+    Some((energy * factor, materials * factor))
 }
 
-fn building_build_cost(building: &Building) -> (Energy, Materials) {
+pub(super) fn building_build_cost(building: &Building) -> (Energy, Materials) {
     match building {
         Building::PowerPlant => (POWER_PLANT_ENERGY_COST, POWER_PLANT_MATERIALS_COST),
         Building::Factory => (FACTORY_ENERGY_COST, FACTORY_MATERIALS_COST),
     }
 }
 
-fn negate_cost(cost: (Energy, Materials)) -> (Energy, Materials) {
+pub(super) fn negate_cost(cost: (Energy, Materials)) -> (Energy, Materials) {
     (-cost.0, -cost.1)
 }
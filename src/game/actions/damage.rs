@@ -1,22 +1,28 @@
-use crate::game::enemies::Enemy;
+use crate::{
+    game::enemies::Enemy,
+    utils::{damage::Damage, status_effects::StatusEffects},
+};
 use bevy::prelude::*;
 
 #[derive(Message)]
 pub struct DamageMessage {
     entity: Entity,
-    damage: f32,
+    damage: Damage,
 }
 
 impl DamageMessage {
-    pub fn new(entity: Entity, damage: f32) -> Self {
+    pub fn new(entity: Entity, damage: Damage) -> Self {
         Self { entity, damage }
     }
 }
 
-pub fn on_damage(mut events: MessageReader<DamageMessage>, mut enemies: Query<&mut Enemy>) {
+pub fn on_damage(
+    mut events: MessageReader<DamageMessage>,
+    mut enemies: Query<(&mut Enemy, &StatusEffects)>,
+) {
     for ev in events.read() {
-        if let Ok(mut enemy) = enemies.get_mut(ev.entity) {
-            enemy.health -= ev.damage;
+        if let Ok((mut enemy, effects)) = enemies.get_mut(ev.entity) {
+            enemy.take_damage(&ev.damage.scaled(effects.damage_multiplier()));
         }
     }
 }
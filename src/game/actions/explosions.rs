@@ -2,6 +2,7 @@ use crate::{
     balance::{ENEMY_DEATH_EXPLOSION_DAMAGE_DIVISOR, ENEMY_DEATH_EXPLOSION_RADIUS_FACTOR},
     game::{enemies::Enemy, GameScreen},
     utils::{
+        damage::{Damage, DamageType},
         explosions::{spawn_explosion, Explosion},
         Vec2Board,
     },
@@ -12,11 +13,11 @@ use bevy::prelude::*;
 pub struct ExplosionMessage {
     pos: Vec2Board,
     radius: f32,
-    damage: f32,
+    damage: Damage,
 }
 
 impl ExplosionMessage {
-    pub fn new(pos: Vec2Board, radius: f32, damage: f32) -> Self {
+    pub fn new(pos: Vec2Board, radius: f32, damage: Damage) -> Self {
         Self {
             pos,
             radius,
@@ -28,7 +29,10 @@ impl ExplosionMessage {
         Self {
             pos: enemy.pos,
             radius: enemy.size_radius * ENEMY_DEATH_EXPLOSION_RADIUS_FACTOR,
-            damage: enemy.health_max / ENEMY_DEATH_EXPLOSION_DAMAGE_DIVISOR,
+            damage: Damage::new(
+                enemy.health_max / ENEMY_DEATH_EXPLOSION_DAMAGE_DIVISOR,
+                DamageType::Explosive,
+            ),
         }
     }
 
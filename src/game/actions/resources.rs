@@ -35,20 +35,29 @@ impl ResourceAnimation {
 }
 
 pub(super) fn on_change_resources(
-    mut cmds: Commands,
     mut events: MessageReader<ResourcesMessage>,
     mut game: ResMut<Game>,
+) {
+    for ev in events.read() {
+        match ev {
+            ResourcesMessage::Energy(energy, _) => game.energy += energy,
+            ResourcesMessage::Materials(materials, _) => game.materials += materials,
+        }
+    }
+}
+
+pub(in crate::game) fn on_resource_animations(
+    mut cmds: Commands,
+    mut events: MessageReader<ResourcesMessage>,
     assets: Res<AssetServer>,
     time: Res<IngameTime>,
 ) {
     for ev in events.read() {
         match ev {
             ResourcesMessage::Energy(energy, pos) => {
-                game.energy += energy;
                 spawn_energy_animation(&mut cmds, *energy, *pos, &assets, time.now())
             }
             ResourcesMessage::Materials(materials, pos) => {
-                game.materials += materials;
                 spawn_materials_animation(&mut cmds, *materials, *pos, &assets, time.now());
             }
         }
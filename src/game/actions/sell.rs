@@ -0,0 +1,153 @@
+use super::{
+    build_menu::{building_build_cost, tower_build_cost},
+    resources::{consume, ResourcesMessage},
+};
+use crate::{
+    balance::{
+        SELL_REFUND_DECAY_PER_MINUTE, SELL_REFUND_FACTOR, SELL_REFUND_MIN_FACTOR,
+        TOWER_UPGRADE_COST_FACTOR,
+    },
+    game::GameScreen,
+    utils::{
+        buildings::{factory::Factory, power_plant::PowerPlant, Building},
+        towers::{Tower, Towerless},
+        BoardPos, Energy, IngameTime, IngameTimestamp, Materials, Vec2Board,
+    },
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub(in crate::game) type QuerySellables<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'a BoardPos,
+        Option<&'a Tower>,
+        Option<&'a PowerPlant>,
+        Option<&'a Factory>,
+    ),
+    With<GameScreen>,
+>;
+
+// Removes the tower or building on the tile, written by the sell menu or a replay
+#[derive(Message, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SellMessage {
+    pub tile_pos: UVec2,
+}
+
+pub(in crate::game) struct Sellable {
+    pub entity: Entity,
+    pub name: String,
+    pub pos: Vec2Board,
+    pub refund: (Energy, Materials),
+}
+
+pub(in crate::game) fn find_sellable(
+    q_sellables: &QuerySellables,
+    tile_pos: UVec2,
+    now: IngameTimestamp,
+) -> Option<Sellable> {
+    q_sellables
+        .iter()
+        .filter(|(_, pos, ..)| ***pos == tile_pos)
+        .find_map(|(entity, _, tower, power_plant, factory)| {
+            let (name, pos, spent, built_at) = match (tower, power_plant, factory) {
+                (Some(tower), _, _) => {
+                    let vals = tower.values();
+                    let name = format!("{:?} tower", Towerless::from(tower));
+                    (name, vals.pos, tower_spent(tower), vals.built_at)
+                }
+                (_, Some(plant), _) => {
+                    let spent = building_build_cost(&Building::PowerPlant);
+                    (
+                        String::from("Power plant"),
+                        plant.pos,
+                        spent,
+                        plant.built_at,
+                    )
+                }
+                (_, _, Some(factory)) => {
+                    let spent = building_build_cost(&Building::Factory);
+                    (
+                        String::from("Factory"),
+                        factory.pos,
+                        spent,
+                        factory.built_at,
+                    )
+                }
+                _ => return None,
+            };
+            let factor = refund_factor(*(now - built_at));
+            Some(Sellable {
+                entity,
+                name,
+                pos,
+                refund: (spent.0 * factor, spent.1 * factor),
+            })
+        })
+}
+
+// Build cost plus all bought upgrades
+fn tower_spent(tower: &Tower) -> (Energy, Materials) {
+    let (energy, materials) = tower_build_cost(tower);
+    let upgrade_levels: u8 = (1..tower.values().level).sum();
+    let factor = 1. + TOWER_UPGRADE_COST_FACTOR * upgrade_levels as f32;
+    (energy * factor, materials * factor)
+}
+
+fn refund_factor(age_secs: f32) -> f32 {
+    (SELL_REFUND_FACTOR - SELL_REFUND_DECAY_PER_MINUTE * age_secs / 60.)
+        .clamp(SELL_REFUND_MIN_FACTOR, SELL_REFUND_FACTOR)
+}
+
+pub(super) fn on_sell(
+    mut evr: MessageReader<SellMessage>,
+    mut cmds: Commands,
+    mut res_actions: MessageWriter<ResourcesMessage>,
+    q_sellables: QuerySellables,
+    time: Res<IngameTime>,
+) {
+    let mut sold = Vec::new();
+    for ev in evr.read() {
+        let Some(sellable) = find_sellable(&q_sellables, ev.tile_pos, time.now()) else {
+            continue;
+        };
+        // Despawns only apply after this system, a second sale would pay twice
+        if sold.contains(&sellable.entity) {
+            continue;
+        }
+        sold.push(sellable.entity);
+        // Children go along, the tile is free for building again
+        cmds.entity(sellable.entity).try_despawn();
+        consume(&mut res_actions, sellable.refund, sellable.pos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::actions::build_menu::tower_upgrade_cost;
+
+    #[test]
+    fn test_refund_decays_to_minimum() {
+        assert_eq!(refund_factor(0.), SELL_REFUND_FACTOR);
+        assert!(refund_factor(60.) < SELL_REFUND_FACTOR);
+        assert_eq!(refund_factor(60. * 60.), SELL_REFUND_MIN_FACTOR);
+    }
+
+    #[test]
+    fn test_tower_spent_adds_upgrade_costs() {
+        let mut tower = Tower::rocket(Vec2Board::new(0.5, 0.5));
+        let (mut energy, mut materials) = tower_build_cost(&tower);
+        while let Some(cost) = tower_upgrade_cost(&tower) {
+            energy += cost.0;
+            materials += cost.1;
+            tower.values_mut().upgrade();
+        }
+        let spent = tower_spent(&tower);
+        assert!((spent.0 - energy).abs() < 0.01);
+        assert!((spent.1 - materials).abs() < 0.01);
+        assert!(spent.0 > tower_build_cost(&tower).0);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::utils::{
+    status_effects::{StatusEffect, StatusEffectKind, StatusEffects},
+    IngameTime,
+};
+use bevy::prelude::*;
+use std::time::Duration;
+
+#[derive(Message)]
+pub struct StatusEffectMessage {
+    entity: Entity,
+    kind: StatusEffectKind,
+    duration: Duration,
+}
+
+impl StatusEffectMessage {
+    pub fn new(entity: Entity, kind: StatusEffectKind, duration: Duration) -> Self {
+        Self {
+            entity,
+            kind,
+            duration,
+        }
+    }
+}
+
+pub fn on_status_effect(
+    mut events: MessageReader<StatusEffectMessage>,
+    mut q_effects: Query<&mut StatusEffects>,
+    time: Res<IngameTime>,
+) {
+    for ev in events.read() {
+        if let Ok(mut effects) = q_effects.get_mut(ev.entity) {
+            effects.add(StatusEffect {
+                kind: ev.kind,
+                until: time.now() + ev.duration,
+            });
+        }
+    }
+}
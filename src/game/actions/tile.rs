@@ -73,8 +73,7 @@ fn toggle_hover_cross(
     tile: &Tile,
 ) {
     match tile {
-        Tile::TowerGround => board_visu.show_hover_cross(query, pos),
-        Tile::BuildingGround => board_visu.show_hover_cross(query, pos),
+        tile if tile.is_buildable() => board_visu.show_hover_cross(query, pos),
         _ => BoardVisu::hide_hover_cross(query),
     }
 }
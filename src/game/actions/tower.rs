@@ -1,22 +1,89 @@
 use crate::{
     game::{
-        statistics::{LaserShotsFired, RocketsFired},
-        GameScreen,
+        actions::{
+            build_menu::{negate_cost, tower_upgrade_cost},
+            resources::{consume, ResourcesMessage},
+        },
+        statistics::{GrenadesFired, LaserShotsFired, RocketsFired, TowerUpgrades},
+        Game, GameScreen,
     },
     utils::{
         shots::{
-            laser::spawn_shot_laser, rocket::spawn_shot_rocket, DamageInRadiusTargetPosShotValues,
-            DamagePerTimeShotValues,
+            grenade::spawn_shot_grenade, laser::spawn_shot_laser, microwave::spawn_shot_microwave,
+            rocket::spawn_shot_rocket, BallisticShotValues, DamageInRadiusTargetPosShotValues,
+            DamagePerTimeShotValues, SlowInRadiusShotValues,
         },
-        IngameTime, Vec2Board,
+        towers::{set_range_circle_radius, Targeting, Tower, TowerRangeCircle},
+        BoardPos, IngameTime, Vec2Board,
     },
 };
 use bevy::prelude::*;
+use bevy_prototype_lyon::entity::Shape;
+use serde::{Deserialize, Serialize};
 
 #[derive(Message)]
+#[allow(clippy::enum_variant_names)]
 pub enum TowerActionsMessage {
     ShootLaser(DamagePerTimeShotValues, Entity),
     ShootRocket(DamageInRadiusTargetPosShotValues, Entity, Vec2Board),
+    ShootMicrowave(SlowInRadiusShotValues),
+    // Lands at the given position
+    ShootGrenade(BallisticShotValues, Vec2Board),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TowerCommand {
+    SetTargeting(Targeting),
+    // Next level, only when the resources suffice
+    Upgrade,
+}
+
+// Player change of a placed tower, written by the tower panel or a replay
+#[derive(Message, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TowerCommandMessage {
+    pub tile_pos: UVec2,
+    pub command: TowerCommand,
+}
+
+pub fn on_tower_commands(
+    mut evr: MessageReader<TowerCommandMessage>,
+    mut q_towers: Query<(&BoardPos, &mut Tower), With<GameScreen>>,
+    mut q_range_circles: Query<(&TowerRangeCircle, &mut Shape), With<GameScreen>>,
+    mut res_actions: MessageWriter<ResourcesMessage>,
+    mut upgrade_count: ResMut<TowerUpgrades>,
+    game: Res<Game>,
+) {
+    // Resource changes only apply after this system, upgrades of the same tick add up
+    let (mut energy_spent, mut materials_spent) = (0., 0.);
+    for ev in evr.read() {
+        let Some((_, mut tower)) = q_towers.iter_mut().find(|(pos, _)| ***pos == ev.tile_pos)
+        else {
+            continue;
+        };
+        match ev.command {
+            TowerCommand::SetTargeting(targeting) => tower.values_mut().targeting = targeting,
+            TowerCommand::Upgrade => {
+                let Some(cost) = tower_upgrade_cost(&tower) else {
+                    continue;
+                };
+                if game.energy() - energy_spent < cost.0
+                    || game.materials() - materials_spent < cost.1
+                {
+                    continue;
+                }
+                energy_spent += cost.0;
+                materials_spent += cost.1;
+                tower.values_mut().upgrade();
+                consume(&mut res_actions, negate_cost(cost), tower.values().pos);
+                let range_radius = tower.values().range_radius;
+                q_range_circles
+                    .iter_mut()
+                    .filter(|(circle, _)| ***circle == ev.tile_pos)
+                    .for_each(|(_, mut shape)| set_range_circle_radius(&mut shape, range_radius));
+                upgrade_count.0 += 1;
+            }
+        }
+    }
 }
 
 pub fn on_tower_actions(
@@ -24,6 +91,8 @@ pub fn on_tower_actions(
     mut actions: MessageReader<TowerActionsMessage>,
     mut laser_count: ResMut<LaserShotsFired>,
     mut rocket_count: ResMut<RocketsFired>,
+    mut grenade_count: ResMut<GrenadesFired>,
+    mut res_actions: MessageWriter<ResourcesMessage>,
     time: Res<IngameTime>,
 ) {
     use TowerActionsMessage::*;
@@ -43,6 +112,62 @@ pub fn on_tower_actions(
                 );
                 rocket_count.0 += 1;
             }
+            ShootMicrowave(shot) => {
+                spawn_shot_microwave::<GameScreen>(&mut cmds, shot.new_shot(time.now()));
+            }
+            ShootGrenade(shot, target_pos) => {
+                spawn_shot_grenade::<GameScreen>(
+                    &mut cmds,
+                    shot.new_shot(*target_pos, time.now()),
+                    time.now(),
+                );
+                res_actions.write(ResourcesMessage::Materials(
+                    -shot.materials_per_shot,
+                    shot.pos_start,
+                ));
+                grenade_count.0 += 1;
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{towers::draw_tower, Difficulty};
+
+    #[test]
+    fn test_same_tick_upgrades_share_resources() {
+        let tower = Tower::laser(Vec2Board::new(0.5, 0.5));
+        let cost = tower_upgrade_cost(&tower).unwrap();
+        let mut game = Game::new(Difficulty::Easy, 1);
+        // Enough for one upgrade only
+        (game.energy, game.materials) = (cost.0 * 1.5, cost.1 * 1.5);
+
+        let mut app = App::new();
+        app.add_message::<TowerCommandMessage>()
+            .add_message::<ResourcesMessage>()
+            .init_resource::<TowerUpgrades>()
+            .insert_resource(game)
+            .add_systems(Update, on_tower_commands);
+        let world = app.world_mut();
+        for x in [0.5, 1.5] {
+            draw_tower::<GameScreen>(&mut world.commands(), Vec2Board::new(x, 0.5), &tower);
+            world.write_message(TowerCommandMessage {
+                tile_pos: UVec2::new(x as u32, 0),
+                command: TowerCommand::Upgrade,
+            });
+        }
+        world.flush();
+        app.update();
+
+        let world = app.world_mut();
+        let levels: Vec<u8> = world
+            .query::<&Tower>()
+            .iter(world)
+            .map(|tower| tower.values().level)
+            .collect();
+        assert_eq!(levels.iter().filter(|level| **level == 2).count(), 1);
+        assert_eq!(world.resource::<TowerUpgrades>().0, 1);
+    }
+}
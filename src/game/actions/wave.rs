@@ -1,5 +1,6 @@
 use crate::{
     balance::NEXT_WAVE_DELAY_SECS,
+    board::BoardCache,
     game::{
         systems::wave::{Wave, WaveState},
         Game,
@@ -22,12 +23,19 @@ pub(in crate::game) fn on_wave_actions(
     mut set_wave_state: ResMut<NextState<WaveState>>,
     mut q_wave_text: Query<&mut Text2d, With<WaveText>>,
     time: Res<IngameTime>,
+    board_cache: Res<BoardCache>,
 ) {
     if !actions.is_empty() {
         for action in actions.read() {
             match action {
                 WaveActionsMessage::StartWave => {
-                    start_wave(&mut cmds, &mut game, &mut set_wave_state, time.now());
+                    start_wave(
+                        &mut cmds,
+                        &mut game,
+                        &mut set_wave_state,
+                        time.now(),
+                        board_cache.road_entrances.len(),
+                    );
                     if let Ok(mut text) = q_wave_text.single_mut() {
                         text.0 = format!("{}", game.wave_no);
                     }
@@ -45,11 +53,12 @@ fn start_wave(
     game: &mut Game,
     set_wave_state: &mut NextState<WaveState>,
     now: IngameTimestamp,
+    entrance_count: usize,
 ) {
     game.next_wave_spawn = None;
     game.wave_no += 1;
 
-    cmds.insert_resource(Wave::new(game.wave_no, now));
+    cmds.insert_resource(Wave::new(game.wave_no, now, entrance_count));
     set_wave_state.set(WaveState::Running);
 }
 
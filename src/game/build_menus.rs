@@ -37,9 +37,14 @@ impl BuildMenu {
     pub fn should_open(&self, tile_pos: UVec2) -> bool {
         !self.is_visible || self.tile_pos != tile_pos
     }
-    fn towers() -> [(BaseLevel, Tower); 2] {
+    fn towers() -> [(BaseLevel, Tower); 4] {
         let pos = Vec2Board::default();
-        [(1, Tower::laser(pos)), (1, Tower::rocket(pos))]
+        [
+            (1, Tower::laser(pos)),
+            (1, Tower::microwave(pos)),
+            (1, Tower::grenade(pos)),
+            (1, Tower::rocket(pos)),
+        ]
     }
 
     fn buidings() -> [(BaseLevel, Building); 2] {
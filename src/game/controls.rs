@@ -8,6 +8,9 @@ use super::{
         GameActionMessage,
     },
     build_menus::BuildMenu,
+    replay::ReplayPlayback,
+    systems::{sell_menu::SellMenu, tower_panel::TowerPanel},
+    test_play::TestPlay,
     GameScreen, HoveredTile, IngameState,
 };
 use crate::{
@@ -29,13 +32,22 @@ pub(super) fn keyboard_input(
     mut bm_scroll_ev: MessageWriter<BuildMenuScrollMessage>,
     mut bm_build_ev: MessageWriter<BuildMenuBuildMessage>,
     ingame_state: Res<State<IngameState>>,
+    playback: Option<Res<ReplayPlayback>>,
+    test_play: Option<Res<TestPlay>>,
 ) {
+    // Esc ends a test play right away
+    if test_play.is_some() && keys.just_released(KeyCode::Escape) {
+        actions.write(GameActionMessage::BackToMainMenu);
+        return;
+    }
+    let is_replay = playback.is_some();
     for binding in KEY_BINDINGS.iter() {
         if keys.just_pressed(binding.key_code) {
             if let Some(control_action) = binding.on_press {
                 write_control_action(
                     control_action,
                     &ingame_state,
+                    is_replay,
                     &mut actions,
                     &mut bm_scroll_ev,
                     &mut bm_build_ev,
@@ -48,6 +60,7 @@ pub(super) fn keyboard_input(
                 write_control_action(
                     control_action,
                     &ingame_state,
+                    is_replay,
                     &mut actions,
                     &mut bm_scroll_ev,
                     &mut bm_build_ev,
@@ -60,6 +73,7 @@ pub(super) fn keyboard_input(
 fn write_control_action(
     control_action: ControlAction,
     ingame_state: &State<IngameState>,
+    is_replay: bool,
     actions: &mut MessageWriter<GameActionMessage>,
     bm_scroll_ev: &mut MessageWriter<BuildMenuScrollMessage>,
     bm_build_ev: &mut MessageWriter<BuildMenuBuildMessage>,
@@ -70,7 +84,8 @@ fn write_control_action(
         ControlAction::Pause => {
             actions.write(Pause);
         }
-        _ if **ingame_state != IngameState::Running => (),
+        // Replays play back their own commands
+        _ if is_replay || **ingame_state != IngameState::Running => (),
         ControlAction::ActivateOverview => {
             actions.write(ActivateOverview);
         }
@@ -123,7 +138,13 @@ pub(super) fn mouse_input(
     hovered_tile: Res<HoveredTile>,
     q_pos: QueryPos,
     tbm: Res<BuildMenu>,
+    mut tower_panel: ResMut<TowerPanel>,
+    mut sell_menu: ResMut<SellMenu>,
 ) {
+    let filled_tile = hovered_tile
+        .0
+        .map(|(pos, _)| pos.as_uvec2())
+        .filter(|upos| q_pos.iter().any(|pos| **pos == *upos));
     match hovered_tile.0 {
         Some((pos, tile)) => tile_hover(
             &mut bm_open_ev,
@@ -134,7 +155,8 @@ pub(super) fn mouse_input(
             ev_scroll,
             &mbi,
             tbm,
-            q_pos,
+            &mut tower_panel,
+            &q_pos,
             pos,
             tile,
         ),
@@ -143,6 +165,11 @@ pub(super) fn mouse_input(
 
     if mbi.just_pressed(CLOSE_BUILD_MENU_MOUSE_BUTTON) {
         bm_close_ev.write(BuildMenuCloseMessage);
+        tower_panel.tile_pos = None;
+        // Right click on a tower or building asks to sell it
+        sell_menu.open(filled_tile);
+    } else if mbi.just_pressed(BUILD_MENU_MOUSE_BUTTON) {
+        sell_menu.open(None);
     }
 }
 
@@ -156,7 +183,8 @@ fn tile_hover(
     ev_scroll: MessageReader<MouseWheel>,
     mbi: &ButtonInput<MouseButton>,
     tbm: Res<BuildMenu>,
-    p_pos: QueryPos,
+    tower_panel: &mut TowerPanel,
+    p_pos: &QueryPos,
     pos: Vec2Board,
     tile: Tile,
 ) {
@@ -164,6 +192,10 @@ fn tile_hover(
     let is_left_click = mbi.just_pressed(BUILD_MENU_MOUSE_BUTTON);
     let is_tile_filled = p_pos.iter().any(|t_pos| upos == **t_pos);
     let is_build_tile = tile.is_buildable();
+    // Clicking a placed tower opens its panel, clicking elsewhere closes it
+    if is_left_click && !tbm.is_open {
+        tower_panel.tile_pos = is_tile_filled.then_some(upos);
+    }
     match (is_left_click, tbm.is_open, is_tile_filled) {
         (true, true, false) => {
             bm_build_ev.write(BuildMenuBuildMessage);
@@ -1,11 +1,7 @@
+use self::definitions::{EnemyDefinition, EnemyDefinitions, EnemyShape};
 use super::GameScreen;
 use crate::{
-    balance::{
-        ENEMY_BREAK_RADIUS_PADDING_FACTOR, NORMAL_ENEMY_HEALTH, NORMAL_ENEMY_SIZE_RADIUS,
-        NORMAL_ENEMY_SPEED_TILES_PER_SECOND, SPEEDER_ENEMY_HEALTH, SPEEDER_ENEMY_SIZE_RADIUS,
-        SPEEDER_ENEMY_SPEED_TILES_PER_SECOND, TANK_ENEMY_HEALTH, TANK_ENEMY_SIZE_RADIUS,
-        TANK_ENEMY_SPEED_TILES_PER_SECOND,
-    },
+    balance::ENEMY_BREAK_RADIUS_PADDING_FACTOR,
     board::{
         spawn_line::SpawnLine,
         step::{BoardDirection, BoardStep},
@@ -13,24 +9,41 @@ use crate::{
         BoardCache,
     },
     utils::{
-        health_bar::health_bar, range_circle::RangeCircle, speed::Speed, TilesPerSecond, Vec2Board,
+        damage::{Damage, Resistances},
+        health_bar::health_bar,
+        interpolation::TickTranslation,
+        range_circle::RangeCircle,
+        speed::Speed,
+        status_effects::{status_effect_indicators, StatusEffects},
+        GameRng, TilesPerSecond, Vec2Board,
     },
 };
-use bevy::color::palettes::css::{DIM_GRAY, MAROON, OLIVE};
+use bevy::color::palettes::css::DIM_GRAY;
 use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
-use rand::random_range;
+use rand::RngExt;
 use serde::{Deserialize, Serialize};
 use std::{cmp::Ordering, ops::RangeInclusive, time::Duration};
 
+pub mod definitions;
+
 pub type IsRoadEnd = bool;
 
-#[derive(Clone, Serialize, Deserialize)]
-pub enum EnemyType {
-    Normal,
-    Speeder,
-    Tank,
-}
+const GROUND_ENEMY_Z: f32 = 1.;
+// Above towers and ground enemies
+const FLYING_ENEMY_Z: f32 = 2.;
+
+// Tiles a flying enemy may start beside its road entrance, and bend of its curve
+const FLIGHT_SPAWN_SPREAD: f32 = 1.5;
+const FLIGHT_MAX_BEND: f32 = 1.5;
+// Segments to measure the curve length
+const FLIGHT_LENGTH_SAMPLES: usize = 16;
+// Walk steps of a position prediction, step changes cost one of them
+const PREDICTION_STEPS: u32 = 20;
+
+// Name of the enemy definition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deref)]
+pub struct EnemyType(pub String);
 
 #[derive(Component, Clone, Serialize, Deserialize)]
 pub struct Enemy {
@@ -39,126 +52,172 @@ pub struct Enemy {
     speed: TilesPerSecond,
     pub health_max: f32,
     pub health: f32,
+    pub resistances: Resistances,
     pub pos: Vec2Board,
     enemy_type: EnemyType,
     current_step: BoardStep,
 
+    // Road entrance and its route in the board cache
+    pub entrance: usize,
+    pub route: usize,
+
     // Tower can reserve damage, so other towers will not shoot at this enemy if damage == health
     pub reserved_damage: f32,
     pub path_offset: f32,
     pub is_in_spawn: bool,
+
+    // Flying enemies ignore the road and fly this way to the road end
+    pub flight: Option<Flight>,
 }
 
-impl Enemy {
-    pub fn new(
-        enemy_type: EnemyType,
-        q_enemies: &Query<&Enemy>,
-        board_cache: &BoardCache,
-    ) -> Option<Self> {
-        let first_step = board_cache.road_path.first().unwrap().clone();
-        match enemy_type {
-            EnemyType::Normal => Self::new_normal(first_step, q_enemies, board_cache),
-            EnemyType::Speeder => Self::new_speeder(first_step, q_enemies, board_cache),
-            EnemyType::Tank => Self::new_tank(first_step, q_enemies, board_cache),
-        }
+// Quadratic curve from the spawn edge to the road end
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Flight {
+    start: Vec2,
+    control: Vec2,
+    end: Vec2,
+    length: f32,
+    distance_flown: f32,
+}
+
+impl Flight {
+    pub fn new(start: Vec2, end: Vec2, bend: f32) -> Self {
+        let control = start.midpoint(end) + (end - start).perp().normalize_or_zero() * bend;
+        let mut flight = Self {
+            start,
+            control,
+            end,
+            length: 0.,
+            distance_flown: 0.,
+        };
+        flight.length = (1..=FLIGHT_LENGTH_SAMPLES)
+            .map(|i| {
+                let t = i as f32 / FLIGHT_LENGTH_SAMPLES as f32;
+                let last_t = (i - 1) as f32 / FLIGHT_LENGTH_SAMPLES as f32;
+                flight.pos_at(t).distance(flight.pos_at(last_t))
+            })
+            .sum();
+        flight
+    }
+
+    fn pos_at(&self, t: f32) -> Vec2 {
+        let inv = 1. - t;
+        inv * inv * self.start + 2. * inv * t * self.control + t * t * self.end
     }
 
-    fn generate_offset(
-        size_radius: f32,
-        q_enemies: &Query<&Enemy>,
-        board_cache: &BoardCache,
-    ) -> Option<f32> {
-        let ranges = Self::find_free_ranges(q_enemies, size_radius, &board_cache.spawn_line);
-        if ranges.is_empty() {
-            None
-        } else {
-            let range = ranges[random_range(0..ranges.len())].clone();
-            Some(random_range(range) - 0.5 - *board_cache.spawn_line.range.start())
+    // Return true if end is reached
+    fn fly(&mut self, distance: f32) -> IsRoadEnd {
+        self.distance_flown += distance;
+        self.distance_flown >= self.length
+    }
+
+    fn pos(&self) -> Vec2Board {
+        match self.length > 0. {
+            true => self.pos_at((self.distance_flown / self.length).min(1.)),
+            false => self.end,
         }
+        .into()
     }
+}
 
-    pub fn new_normal(
-        mut current_step: BoardStep,
+impl Enemy {
+    pub fn new(
+        definition: &EnemyDefinition,
+        entrance: usize,
         q_enemies: &Query<&Enemy>,
         board_cache: &BoardCache,
+        rng: &mut GameRng,
     ) -> Option<Self> {
-        let size_radius = NORMAL_ENEMY_SIZE_RADIUS;
-        if let Some(path_offset) = Self::generate_offset(size_radius, q_enemies, board_cache) {
-            current_step.distance += 0.5;
-            let pos = first_pos(&current_step, path_offset);
-            current_step.start_pos = pos;
-            let speed = NORMAL_ENEMY_SPEED_TILES_PER_SECOND;
-            return Some(Self {
-                size_radius,
-                break_radius: size_radius + (size_radius * ENEMY_BREAK_RADIUS_PADDING_FACTOR),
-                speed,
-                health_max: NORMAL_ENEMY_HEALTH,
-                health: NORMAL_ENEMY_HEALTH,
-                pos,
-                enemy_type: EnemyType::Normal,
-                current_step,
-                reserved_damage: 0.,
-                path_offset,
-                is_in_spawn: true,
-            });
+        let route = match board_cache.road_entrances[entrance].road_paths.len() {
+            0 => return None,
+            1 => 0,
+            len => rng.random_range(0..len),
+        };
+        let mut current_step = board_cache
+            .road_path(entrance, route)
+            .first()
+            .unwrap()
+            .clone();
+        if definition.is_flying {
+            return Self::new_flying(definition, entrance, current_step, board_cache, rng);
         }
-        None
+        let spawn_line = &board_cache.road_entrances[entrance].spawn_line;
+        let size_radius = definition.size_radius;
+        let path_offset = Self::generate_offset(size_radius, entrance, q_enemies, spawn_line, rng)?;
+        current_step.distance += 0.5;
+        let pos = first_pos(&current_step, path_offset);
+        current_step.start_pos = pos;
+        Some(Self {
+            size_radius,
+            break_radius: size_radius + (size_radius * ENEMY_BREAK_RADIUS_PADDING_FACTOR),
+            speed: definition.speed,
+            health_max: definition.health,
+            health: definition.health,
+            resistances: definition.resistances.clone(),
+            pos,
+            enemy_type: EnemyType(definition.name.clone()),
+            current_step,
+            entrance,
+            route,
+            reserved_damage: 0.,
+            path_offset,
+            is_in_spawn: true,
+            flight: None,
+        })
     }
 
-    pub fn new_speeder(
-        mut current_step: BoardStep,
-        q_enemies: &Query<&Enemy>,
+    // Starts just outside the board beside the road entrance, the spawn line is only for the road
+    fn new_flying(
+        definition: &EnemyDefinition,
+        entrance: usize,
+        first_step: BoardStep,
         board_cache: &BoardCache,
+        rng: &mut GameRng,
     ) -> Option<Self> {
-        let size_radius = SPEEDER_ENEMY_SIZE_RADIUS;
-        if let Some(path_offset) = Self::generate_offset(size_radius, q_enemies, board_cache) {
-            current_step.distance += 0.5;
-            let pos = first_pos(&current_step, path_offset);
-            current_step.start_pos = pos;
-            let speed = SPEEDER_ENEMY_SPEED_TILES_PER_SECOND;
-            return Some(Self {
-                size_radius,
-                break_radius: size_radius + (size_radius * ENEMY_BREAK_RADIUS_PADDING_FACTOR),
-                speed,
-                health_max: SPEEDER_ENEMY_HEALTH,
-                health: SPEEDER_ENEMY_HEALTH,
-                pos,
-                enemy_type: EnemyType::Speeder,
-                current_step,
-                reserved_damage: 0.,
-                path_offset,
-                is_in_spawn: true,
-            });
-        }
-        None
+        let end: Vec2 = Vec2Board::from_uvec2_middle(&board_cache.road_end_pos?).into();
+        let inwards: Vec2 = first_step.direction.as_vec2board().into();
+        let entrance_pos: Vec2 =
+            Vec2Board::from_uvec2_middle(&board_cache.road_entrances[entrance].pos).into();
+        let start = entrance_pos - inwards
+            + inwards.perp() * rng.random_range(-FLIGHT_SPAWN_SPREAD..=FLIGHT_SPAWN_SPREAD);
+        let flight = Flight::new(
+            start,
+            end,
+            rng.random_range(-FLIGHT_MAX_BEND..=FLIGHT_MAX_BEND),
+        );
+        Some(Self {
+            size_radius: definition.size_radius,
+            break_radius: definition.size_radius,
+            speed: definition.speed,
+            health_max: definition.health,
+            health: definition.health,
+            resistances: definition.resistances.clone(),
+            pos: flight.pos(),
+            enemy_type: EnemyType(definition.name.clone()),
+            current_step: first_step,
+            entrance,
+            route: 0,
+            reserved_damage: 0.,
+            path_offset: 0.,
+            is_in_spawn: false,
+            flight: Some(flight),
+        })
     }
 
-    pub fn new_tank(
-        mut current_step: BoardStep,
+    fn generate_offset(
+        size_radius: f32,
+        entrance: usize,
         q_enemies: &Query<&Enemy>,
-        board_cache: &BoardCache,
-    ) -> Option<Self> {
-        let size_radius = TANK_ENEMY_SIZE_RADIUS;
-        if let Some(path_offset) = Self::generate_offset(size_radius, q_enemies, board_cache) {
-            current_step.distance += 0.5;
-            let pos = first_pos(&current_step, path_offset);
-            current_step.start_pos = pos;
-            let speed = TANK_ENEMY_SPEED_TILES_PER_SECOND;
-            return Some(Self {
-                size_radius,
-                break_radius: size_radius + (size_radius * ENEMY_BREAK_RADIUS_PADDING_FACTOR),
-                speed,
-                health_max: TANK_ENEMY_HEALTH,
-                health: TANK_ENEMY_HEALTH,
-                pos,
-                enemy_type: EnemyType::Tank,
-                current_step,
-                reserved_damage: 0.,
-                path_offset,
-                is_in_spawn: true,
-            });
+        spawn_line: &SpawnLine,
+        rng: &mut GameRng,
+    ) -> Option<f32> {
+        let ranges = Self::find_free_ranges(q_enemies, size_radius, entrance, spawn_line);
+        if ranges.is_empty() {
+            None
+        } else {
+            let range = ranges[rng.random_range(0..ranges.len())].clone();
+            Some(rng.random_range(range) - 0.5 - *spawn_line.range.start())
         }
-        None
     }
 
     #[allow(dead_code)]
@@ -169,12 +228,16 @@ impl Enemy {
             speed: 1.,
             health_max: 100.,
             health: 100.,
+            resistances: Resistances::default(),
             pos,
-            enemy_type: EnemyType::Normal,
+            enemy_type: EnemyType(String::from("Normal")),
             current_step: BoardStep::default(),
+            entrance: 0,
+            route: 0,
             reserved_damage: 0.,
             path_offset: 0.,
             is_in_spawn: false,
+            flight: None,
         }
     }
 
@@ -189,6 +252,11 @@ impl Enemy {
         speed: TilesPerSecond,
         board_cache: &BoardCache,
     ) -> IsRoadEnd {
+        if let Some(flight) = self.flight.as_mut() {
+            let is_end = flight.fly(Self::distance_walked(speed, dur));
+            self.pos = flight.pos();
+            return is_end;
+        }
         match self.current_step.is_end_reached() {
             true => self.step_end_reached(board_cache),
             false => self.walk(dur, speed),
@@ -197,7 +265,11 @@ impl Enemy {
 
     fn step_end_reached(&mut self, board_cache: &BoardCache) -> IsRoadEnd {
         let step = &mut self.current_step;
-        if let Some(next) = next_step(&board_cache.road_path, step, self.path_offset) {
+        if let Some(next) = next_step(
+            board_cache.road_path(self.entrance, self.route),
+            step,
+            self.path_offset,
+        ) {
             self.pos = next.start_pos;
             *step = next;
             return false;
@@ -213,31 +285,83 @@ impl Enemy {
         false
     }
 
+    // Where the enemy will be at the current speed, for shots that don't follow it
+    pub fn predicted_pos(
+        &self,
+        dur: Duration,
+        speed: TilesPerSecond,
+        board_cache: &BoardCache,
+    ) -> Vec2Board {
+        let mut enemy = self.clone();
+        let step_dur = dur / PREDICTION_STEPS;
+        for _ in 0..PREDICTION_STEPS {
+            if enemy.walk_until_end(step_dur, speed, board_cache) {
+                break;
+            }
+        }
+        enemy.pos
+    }
+
+    // Way left to the road end, the lowest one is furthest along
+    pub fn distance_to_end(&self, board_cache: &BoardCache) -> f32 {
+        match &self.flight {
+            Some(flight) => flight.length - flight.distance_flown,
+            None => remaining_distance(
+                board_cache.road_path(self.entrance, self.route),
+                &self.current_step,
+            ),
+        }
+    }
+
     pub fn is_in_range(&self, tower_pos: Vec2Board, range_radius: f32) -> bool {
         self.pos.distance(tower_pos.into()) <= range_radius
     }
 
+    pub fn take_damage(&mut self, damage: &Damage) {
+        self.health -= self.resistances.apply(damage);
+    }
+
+    pub fn is_flying(&self) -> bool {
+        self.flight.is_some()
+    }
+
+    pub fn z(&self) -> f32 {
+        match self.is_flying() {
+            true => FLYING_ENEMY_Z,
+            false => GROUND_ENEMY_Z,
+        }
+    }
+
     pub fn health_as_percent(&self) -> f32 {
         self.health / self.health_max
     }
 
-    pub fn spawn(self, cmds: &mut Commands) {
-        match self.enemy_type {
-            EnemyType::Normal => spawn_normal_enemy(cmds, self),
-            EnemyType::Speeder => todo!(),
-            EnemyType::Tank => spawn_tank_enemy(cmds, self),
-        }
+    pub fn enemy_type(&self) -> &EnemyType {
+        &self.enemy_type
+    }
+
+    // Enemies of unknown kinds, like from old save games, still get drawn
+    pub fn spawn(self, cmds: &mut Commands, definitions: &EnemyDefinitions) -> Entity {
+        let shape = definitions
+            .get(&self.enemy_type)
+            .map(|definition| definition.shape.clone())
+            .unwrap_or_default();
+        spawn_enemy(cmds, self, &shape)
     }
 
     fn find_free_ranges(
         q_enemies: &Query<&Enemy>,
         mut new_enemy_radius: f32,
+        entrance: usize,
         spawn_line: &SpawnLine,
     ) -> Vec<RangeInclusive<f32>> {
         new_enemy_radius *= 2.;
         let start_range = (spawn_line.range.start() + new_enemy_radius)
             ..=(spawn_line.range.end() - new_enemy_radius);
-        let enemies: Vec<&Enemy> = q_enemies.iter().filter(|enemy| enemy.is_in_spawn).collect();
+        let enemies: Vec<&Enemy> = q_enemies
+            .iter()
+            .filter(|enemy| enemy.is_in_spawn && enemy.entrance == entrance)
+            .collect();
         if enemies.is_empty() {
             vec![start_range]
         } else {
@@ -270,10 +394,14 @@ impl Enemy {
         Some(start..=end)
     }
 
-    pub fn is_behind_of(&self, other: &Enemy) -> bool {
+    pub fn is_behind_of(&self, other: &Enemy, board_cache: &BoardCache) -> bool {
         let s_step = &self.current_step;
         let o_step = &other.current_step;
 
+        // Step indices of different routes don't match after a fork or merge
+        if (self.entrance, self.route) != (other.entrance, other.route) {
+            return self.distance_to_end(board_cache) > other.distance_to_end(board_cache);
+        }
         o_step.road_path_index > s_step.road_path_index
             || (o_step.road_path_index == s_step.road_path_index
                 && self.is_behind_of_in_step(other))
@@ -389,11 +517,16 @@ mod gaps_tests {
 #[cfg(test)]
 mod enemy_tests {
     use crate::{
-        board::step::{BoardDirection, BoardStep},
+        board::{
+            map_file::get_all_boards_in_folder,
+            step::{BoardDirection, BoardStep},
+            BoardCache,
+        },
         utils::Vec2Board,
     };
 
-    use super::{first_pos, next_step, Enemy};
+    use super::{first_pos, next_step, remaining_distance, Enemy, Flight};
+    use bevy::math::Vec2;
 
     #[test]
     fn test_set_range_to_padding_range_inside() {
@@ -450,7 +583,7 @@ mod enemy_tests {
         let enemy_1 = Enemy::new_dummy(Vec2Board::new(1., 0.));
         let mut enemy_2 = Enemy::new_dummy(Vec2Board::new(2., 0.));
         enemy_2.current_step.distance_walked = 1.;
-        assert!(enemy_1.is_behind_of(&enemy_2));
+        assert!(enemy_1.is_behind_of(&enemy_2, &test_board_cache()));
     }
 
     #[test]
@@ -458,7 +591,75 @@ mod enemy_tests {
         let enemy_1 = Enemy::new_dummy(Vec2Board::new(1., 0.));
         let mut enemy_2 = Enemy::new_dummy(Vec2Board::new(4., 0.));
         enemy_2.current_step.distance_walked = 4.;
-        assert!(enemy_1.is_behind_of(&enemy_2));
+        assert!(enemy_1.is_behind_of(&enemy_2, &test_board_cache()));
+    }
+
+    fn test_board_cache() -> BoardCache {
+        let board = get_all_boards_in_folder()
+            .unwrap()
+            .boards
+            .into_iter()
+            .find(|board| board.name == "0test")
+            .unwrap();
+        BoardCache::new(&board)
+    }
+
+    #[test]
+    fn test_is_behind_after_merge_and_u_turn() {
+        let step = |index, path: (f32, f32), start: (f32, f32)| {
+            BoardStep::new(
+                index,
+                Vec2Board::new(path.0, path.1),
+                Vec2Board::new(start.0, start.1),
+            )
+        };
+        // Both routes share the way east, north and back west
+        let shared = |index| {
+            vec![
+                step(index, (4., 0.), (4., 0.)),
+                step(index + 1, (0., 2.), (8., 0.)),
+                step(index + 2, (-4., 0.), (8., 2.)),
+            ]
+        };
+        let mut board_cache = test_board_cache();
+        board_cache.road_entrances[0].road_paths = vec![
+            [vec![step(0, (4., 0.), (0., 0.))], shared(1)].concat(),
+            [vec![step(0, (0., 2.), (4., -2.))], shared(1)].concat(),
+        ];
+
+        let mut ahead = Enemy::new_dummy(Vec2Board::new(6., 2.));
+        ahead.current_step = board_cache.road_path(0, 0)[3].clone();
+        ahead.current_step.distance_walked = 2.;
+        let mut behind = Enemy::new_dummy(Vec2Board::new(7., 0.));
+        behind.route = 1;
+        behind.current_step = board_cache.road_path(0, 1)[1].clone();
+        behind.current_step.distance_walked = 3.;
+
+        assert!(behind.is_behind_of(&ahead, &board_cache));
+        assert!(!ahead.is_behind_of(&behind, &board_cache));
+    }
+
+    #[test]
+    fn test_remaining_distance_counts_later_steps() {
+        let path = vec![
+            BoardStep::new(0, Vec2Board::new(3., 0.), Vec2Board::new(0., 0.)),
+            BoardStep::new(1, Vec2Board::new(0., 2.), Vec2Board::new(3., 0.)),
+            BoardStep::new(2, Vec2Board::new(4., 0.), Vec2Board::new(3., 2.)),
+        ];
+        let mut step = path[1].clone();
+        step.distance_walked = 0.5;
+        assert_eq!(remaining_distance(&path, &step), 5.5);
+        assert_eq!(remaining_distance(&path, &path[0]), 9.);
+    }
+
+    #[test]
+    fn test_flight_bends_and_reaches_end() {
+        let mut flight = Flight::new(Vec2::new(0., 0.), Vec2::new(4., 0.), 1.);
+        assert!(flight.length > 4.);
+        assert!(!flight.fly(flight.length / 2.));
+        assert!(flight.pos().y > 0.4);
+        assert!(flight.fly(flight.length / 2.));
+        assert_eq!(flight.pos(), Vec2Board::new(4., 0.));
     }
 
     #[test]
@@ -530,60 +731,46 @@ mod enemy_tests {
         );
     }
 }
-pub(super) fn spawn_normal_enemy(cmds: &mut Commands, enemy: Enemy) {
-    cmds.spawn(enemy_normal_shape(&enemy))
+fn spawn_enemy(cmds: &mut Commands, enemy: Enemy, shape: &EnemyShape) -> Entity {
+    cmds.spawn(enemy_shape(&enemy, shape))
         .with_children(|parent| {
-            health_bar(parent, TILE_SIZE / 8.);
+            health_bar(parent, enemy.size_radius * TILE_SIZE);
+            status_effect_indicators(parent, enemy.size_radius * TILE_SIZE);
         })
         .insert(Speed::new(enemy.speed))
+        .insert(StatusEffects::default())
+        .insert(TickTranslation::new(enemy.pos.to_scaled_vec3(enemy.z())))
         .insert(enemy)
-        .insert(GameScreen);
+        .insert(GameScreen)
+        .id()
 }
 
-fn enemy_normal_shape(enemy: &Enemy) -> impl Bundle {
+fn enemy_shape(enemy: &Enemy, shape: &EnemyShape) -> impl Bundle {
     let line_width = TILE_SIZE / 24.;
     (
         ShapeBuilder::with(&shapes::RegularPolygon {
-            sides: 5,
+            sides: shape.sides,
             feature: shapes::RegularPolygonFeature::Radius(
                 enemy.size_radius * TILE_SIZE - (line_width / 2.),
             ),
             ..default()
         })
-        .fill(MAROON)
+        .fill(shape.color())
         .stroke(Stroke::new(DIM_GRAY, line_width))
         .build(),
-        Transform::from_translation(enemy.pos.to_scaled_vec3(1.)),
+        Transform::from_translation(enemy.pos.to_scaled_vec3(enemy.z())),
     )
 }
 
-pub(super) fn spawn_tank_enemy(cmds: &mut Commands, enemy: Enemy) {
-    cmds.spawn(enemy_tank_shape(&enemy))
-        .with_children(|parent| {
-            health_bar(parent, enemy.size_radius * TILE_SIZE);
-        })
-        .insert(Speed::new(enemy.speed))
-        .insert(enemy)
-        .insert(GameScreen);
+fn remaining_distance(path: &[BoardStep], step: &BoardStep) -> f32 {
+    let steps_after: f32 = path
+        .iter()
+        .skip(step.road_path_index + 1)
+        .map(|step| step.distance)
+        .sum();
+    steps_after + (step.distance - step.distance_walked).max(0.)
 }
 
-fn enemy_tank_shape(enemy: &Enemy) -> impl Bundle {
-    let line_width = TILE_SIZE / 24.;
-
-    (
-        ShapeBuilder::with(&shapes::RegularPolygon {
-            sides: 6,
-            feature: shapes::RegularPolygonFeature::Radius(
-                enemy.size_radius * TILE_SIZE - (line_width / 2.),
-            ),
-            ..shapes::RegularPolygon::default()
-        })
-        .fill(OLIVE)
-        .stroke(Stroke::new(DIM_GRAY, line_width))
-        .build(),
-        Transform::from_translation(enemy.pos.to_scaled_vec3(1.)),
-    )
-}
 pub fn next_step(path: &[BoardStep], last: &BoardStep, offset: f32) -> Option<BoardStep> {
     if let Some(next) = path.get(last.road_path_index + 1) {
         let mut new_step = next.clone();
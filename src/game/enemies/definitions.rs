@@ -0,0 +1,129 @@
+use super::EnemyType;
+use crate::utils::{damage::Resistances, TilesPerSecond};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+// Built in, so replays and headless runs always see the same enemies
+const ENEMY_DEFINITIONS_JSON: &str = include_str!("../../../assets/enemies.json");
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnemyShape {
+    pub sides: usize,
+    // Hex colour like "#800000"
+    pub color: String,
+}
+
+impl EnemyShape {
+    pub fn color(&self) -> Color {
+        Srgba::hex(&self.color).unwrap_or(Srgba::WHITE).into()
+    }
+}
+
+impl Default for EnemyShape {
+    fn default() -> Self {
+        Self {
+            sides: 4,
+            color: String::from("#FFFFFF"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnemyDefinition {
+    pub name: String,
+    pub size_radius: f32,
+    pub speed: TilesPerSecond,
+    pub health: f32,
+    // Materials for the kill
+    pub reward: f32,
+    // Energy and materials lost when reaching the base with full health
+    pub base_damage: f32,
+    pub shape: EnemyShape,
+    #[serde(default)]
+    pub resistances: Resistances,
+    // Every nth enemy of a wave is of this kind, the highest matching n wins
+    pub spawn_every_nth: u32,
+    pub first_wave: u32,
+    // Flies straight to the road end, only towers which hit air can shoot it
+    #[serde(default)]
+    pub is_flying: bool,
+}
+
+#[derive(Resource, Debug, Clone, Deref)]
+pub struct EnemyDefinitions(Vec<EnemyDefinition>);
+
+impl EnemyDefinitions {
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        let definitions: Vec<EnemyDefinition> = serde_json::from_str(json)?;
+        if definitions.is_empty() {
+            return Err("Need at least one enemy definition".into());
+        }
+        for (i, definition) in definitions.iter().enumerate() {
+            let name = &definition.name;
+            if definitions[..i].iter().any(|other| other.name == *name) {
+                return Err(format!("Enemy '{name}' is defined twice").into());
+            }
+            if definition.shape.sides < 3 {
+                return Err(format!("Enemy '{name}' needs at least three sides").into());
+            }
+            if Srgba::hex(&definition.shape.color).is_err() {
+                return Err(format!("Enemy '{name}' has an invalid colour").into());
+            }
+            if definition.spawn_every_nth == 0 {
+                return Err(format!("Enemy '{name}' must spawn at least every 1st time").into());
+            }
+        }
+        Ok(Self(definitions))
+    }
+
+    pub fn get(&self, enemy_type: &EnemyType) -> Option<&EnemyDefinition> {
+        self.iter()
+            .find(|definition| definition.name == **enemy_type)
+    }
+
+    // Enemy for the nth spawn of a wave, starting with 1
+    pub fn select(&self, wave_no: u32, spawn_no: u32) -> &EnemyDefinition {
+        self.iter()
+            .filter(|definition| {
+                wave_no >= definition.first_wave
+                    && spawn_no.is_multiple_of(definition.spawn_every_nth)
+            })
+            .max_by_key(|definition| definition.spawn_every_nth)
+            .unwrap_or(&self[0])
+    }
+}
+
+impl Default for EnemyDefinitions {
+    fn default() -> Self {
+        Self::from_json(ENEMY_DEFINITIONS_JSON).expect("Built in enemy definitions are invalid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selected(definitions: &EnemyDefinitions, wave_no: u32) -> Vec<&str> {
+        (1..=12)
+            .map(|spawn_no| definitions.select(wave_no, spawn_no).name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_built_in_definitions_select_all_enemies() {
+        let definitions = EnemyDefinitions::default();
+        let first_wave = selected(&definitions, 1);
+        assert!(!first_wave.contains(&"Speeder"));
+        assert_eq!(first_wave[9], "Tank");
+        let later_wave = selected(&definitions, 3);
+        assert_eq!(later_wave[3], "Speeder");
+        assert_eq!(later_wave[0], "Normal");
+    }
+
+    #[test]
+    fn test_duplicate_names_are_invalid() {
+        let json = ENEMY_DEFINITIONS_JSON.replace("\"Tank\"", "\"Normal\"");
+        assert!(EnemyDefinitions::from_json(&json).is_err());
+    }
+}
@@ -0,0 +1,362 @@
+use super::{
+    actions::{
+        build::BuildMessage, sell::SellMessage, tower::TowerCommandMessage, GameActionMessage,
+    },
+    save::SaveGame,
+    test_play::TestPlay,
+    Game,
+};
+use crate::{
+    board::Board,
+    utils::{file_name_of, Difficulty, IngameTime, IngameTimestamp, Seed},
+};
+use bevy::{prelude::*, window::WindowCloseRequested};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fs::{create_dir_all, read_dir, read_to_string, File},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const REPLAYS_DIR: &str = "./replays/";
+
+// Increase on every change of the replay format, older replays get rejected
+pub const REPLAY_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ReplayCommand {
+    Build(BuildMessage),
+    Game(GameActionMessage),
+    Tower(TowerCommandMessage),
+    Sell(SellMessage),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayEntry {
+    pub time: IngameTimestamp,
+    pub command: ReplayCommand,
+}
+
+// Player commands of one match, same seed and commands play the same match again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    version: u32,
+    pub board: Board,
+    pub difficulty: Difficulty,
+    pub seed: Seed,
+    pub entries: Vec<ReplayEntry>,
+}
+
+#[derive(Deserialize)]
+struct ReplayVersion {
+    version: u32,
+}
+
+impl Replay {
+    pub fn new(board: Board, difficulty: Difficulty, seed: Seed) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            board,
+            difficulty,
+            seed,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, Box<dyn Error>> {
+        read_to_string(path)
+            .map_err(Box::from)
+            .and_then(|json| Self::from_json(&json))
+            .map_err(|err| format!("Invalid replay file '{}': {err}", path.display()).into())
+    }
+
+    fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        let ReplayVersion { version } = serde_json::from_str(json)?;
+        if version != REPLAY_VERSION {
+            return Err(format!(
+                "Version {version} is not supported, only version {REPLAY_VERSION} is"
+            )
+            .into());
+        }
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_file(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir)?;
+        }
+        let mut output = File::create(path)?;
+        write!(output, "{}", serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn new_game(&self) -> Game {
+        Game::new(self.difficulty, self.seed)
+    }
+
+    fn push(&mut self, time: IngameTimestamp, command: ReplayCommand) {
+        self.entries.push(ReplayEntry { time, command });
+    }
+}
+
+// Loaded replays of the replays folder, a broken file doesn't hide the other replays
+#[derive(Default)]
+pub struct ReplayFolder {
+    pub replays: Vec<(PathBuf, Replay)>,
+    pub errors: Vec<String>,
+}
+
+impl ReplayFolder {
+    pub fn err_text(&self) -> Option<String> {
+        match self.errors.is_empty() {
+            true => None,
+            false => Some(self.errors.join("\n")),
+        }
+    }
+}
+
+pub fn get_all_replays_in_folder() -> Result<ReplayFolder, Box<dyn Error>> {
+    let mut folder = ReplayFolder::default();
+    if !Path::new(REPLAYS_DIR).exists() {
+        return Ok(folder);
+    }
+    for dir_entry in read_dir(REPLAYS_DIR)? {
+        let path = match dir_entry {
+            Ok(dir_entry) => dir_entry.path(),
+            Err(err) => {
+                folder.errors.push(err.to_string());
+                continue;
+            }
+        };
+        match Replay::from_file(&path) {
+            Ok(replay) => folder.replays.push((path, replay)),
+            Err(err) => folder.errors.push(err.to_string()),
+        }
+    }
+    Ok(folder)
+}
+
+#[derive(Resource)]
+pub(super) struct ReplayRecorder {
+    path: PathBuf,
+    replay: Replay,
+}
+
+impl ReplayRecorder {
+    fn write(&self) {
+        if let Err(err) = self.replay.to_file(&self.path) {
+            error!("Failed to write replay '{}': {err}", self.path.display());
+        }
+    }
+}
+
+// Commands of a replay that still wait for their time
+#[derive(Resource)]
+pub(crate) struct ReplayPlayback {
+    builds: VecDeque<(IngameTimestamp, BuildMessage)>,
+    tower_commands: VecDeque<(IngameTimestamp, TowerCommandMessage)>,
+    sells: VecDeque<(IngameTimestamp, SellMessage)>,
+    game_actions: VecDeque<(IngameTimestamp, GameActionMessage)>,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: &Replay) -> Self {
+        let mut playback = Self {
+            builds: VecDeque::new(),
+            tower_commands: VecDeque::new(),
+            sells: VecDeque::new(),
+            game_actions: VecDeque::new(),
+        };
+        for entry in replay.entries.iter() {
+            match &entry.command {
+                ReplayCommand::Build(build) => playback.builds.push_back((entry.time, *build)),
+                ReplayCommand::Tower(command) => {
+                    playback.tower_commands.push_back((entry.time, *command))
+                }
+                ReplayCommand::Sell(sell) => playback.sells.push_back((entry.time, *sell)),
+                ReplayCommand::Game(action) => playback
+                    .game_actions
+                    .push_back((entry.time, action.clone())),
+            }
+        }
+        playback
+    }
+
+    // Speed and pause only change how fast ticks run, not the match itself
+    pub fn without_game_actions(mut self) -> Self {
+        self.game_actions.clear();
+        self
+    }
+}
+
+// Records only fresh matches, neither resumed saves, replays nor test plays
+pub(super) fn start_replay_recording(
+    mut cmds: Commands,
+    game: Res<Game>,
+    board: Res<Board>,
+    playback: Option<Res<ReplayPlayback>>,
+    save: Option<Res<SaveGame>>,
+    test_play: Option<Res<TestPlay>>,
+) {
+    if playback.is_some() || save.is_some() || test_play.is_some() {
+        return;
+    }
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|dur| dur.as_secs())
+        .unwrap_or_default();
+    cmds.insert_resource(ReplayRecorder {
+        path: Path::new(REPLAYS_DIR)
+            .join(format!("{}_{secs}_replay.json", file_name_of(&board.name))),
+        replay: Replay::new(board.clone(), game.difficulty, game.seed),
+    });
+}
+
+pub(super) fn record_commands(
+    mut builds: MessageReader<BuildMessage>,
+    mut tower_commands: MessageReader<TowerCommandMessage>,
+    mut sells: MessageReader<SellMessage>,
+    mut recorder: ResMut<ReplayRecorder>,
+    time: Res<IngameTime>,
+) {
+    for build in builds.read() {
+        recorder
+            .replay
+            .push(time.now(), ReplayCommand::Build(*build));
+    }
+    for command in tower_commands.read() {
+        recorder
+            .replay
+            .push(time.now(), ReplayCommand::Tower(*command));
+    }
+    for sell in sells.read() {
+        recorder.replay.push(time.now(), ReplayCommand::Sell(*sell));
+    }
+}
+
+pub(super) fn record_game_actions(
+    mut events: MessageReader<GameActionMessage>,
+    mut recorder: ResMut<ReplayRecorder>,
+    time: Res<IngameTime>,
+) {
+    use GameActionMessage::*;
+    for action in events.read() {
+        if matches!(action, SpeedUp | SpeedDown | Speed(_) | Pause | Continue) {
+            recorder
+                .replay
+                .push(time.now(), ReplayCommand::Game(action.clone()));
+        }
+    }
+}
+
+pub(super) fn write_replay(recorder: Option<Res<ReplayRecorder>>) {
+    if let Some(recorder) = recorder {
+        recorder.write();
+    }
+}
+
+pub(super) fn write_replay_on_window_close(
+    mut events: MessageReader<WindowCloseRequested>,
+    recorder: Res<ReplayRecorder>,
+) {
+    if events.read().count() > 0 {
+        recorder.write();
+    }
+}
+
+pub(super) fn play_commands(
+    mut build_ev: MessageWriter<BuildMessage>,
+    mut tower_command_ev: MessageWriter<TowerCommandMessage>,
+    mut sell_ev: MessageWriter<SellMessage>,
+    mut playback: ResMut<ReplayPlayback>,
+    time: Res<IngameTime>,
+) {
+    let now = time.now();
+    while let Some((_, build)) = playback.builds.pop_front_if(|(at, _)| *at <= now) {
+        build_ev.write(build);
+    }
+    while let Some((_, command)) = playback.tower_commands.pop_front_if(|(at, _)| *at <= now) {
+        tower_command_ev.write(command);
+    }
+    while let Some((_, sell)) = playback.sells.pop_front_if(|(at, _)| *at <= now) {
+        sell_ev.write(sell);
+    }
+}
+
+pub(super) fn play_game_actions(
+    mut actions: MessageWriter<GameActionMessage>,
+    mut playback: ResMut<ReplayPlayback>,
+    time: Res<IngameTime>,
+) {
+    // One per frame, so a pause is seen before the following continue
+    if let Some((_, action)) = playback
+        .game_actions
+        .pop_front_if(|(at, _)| *at <= time.now())
+    {
+        actions.write(action);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        board::map_file::get_all_boards_in_folder,
+        game::actions::build::BuildItem,
+        headless::{headless_app, HeadlessConfig},
+        utils::{towers::Towerless, BoardPos},
+    };
+    use std::env::temp_dir;
+
+    fn play(replay: &Replay) -> App {
+        let path = temp_dir().join(format!("base_defense_{}_replay.json", replay.seed));
+        replay.to_file(&path).unwrap();
+        let args = ["--replay", path.to_str().unwrap()].map(String::from);
+        let mut app = headless_app(HeadlessConfig::from_args(&args).unwrap()).unwrap();
+        app.update();
+        while app.world().resource::<IngameTime>().elapsed_secs() < 2. {
+            app.update();
+        }
+        app
+    }
+
+    #[test]
+    fn test_broken_replay_error_names_file() {
+        let path = temp_dir().join("base_defense_broken_replay.json");
+        std::fs::write(&path, "{").unwrap();
+        let err = Replay::from_file(&path).unwrap_err().to_string();
+        assert!(err.contains(&path.display().to_string()));
+    }
+
+    #[test]
+    fn test_replay_plays_back_builds() {
+        let board = get_all_boards_in_folder()
+            .unwrap()
+            .boards
+            .into_iter()
+            .find(|board| board.name == "0test")
+            .unwrap();
+        let mut replay = Replay::new(board, Difficulty::Easy, 21);
+        let tile_pos = UVec2::new(0, 0);
+        replay.push(
+            IngameTimestamp::new(1.),
+            ReplayCommand::Build(BuildMessage {
+                tile_pos,
+                item: BuildItem::Tower(Towerless::Laser),
+            }),
+        );
+        let mut app_with_build = play(&replay);
+        replay.entries.clear();
+        let app_without_build = play(&replay);
+
+        let world = app_with_build.world_mut();
+        let mut q_towers = world.query::<&BoardPos>();
+        assert!(q_towers.iter(world).any(|pos| **pos == tile_pos));
+        let game_with_build = app_with_build.world().resource::<Game>();
+        let game_without_build = app_without_build.world().resource::<Game>();
+        assert!(game_with_build.materials() < game_without_build.materials());
+    }
+}
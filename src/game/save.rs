@@ -0,0 +1,305 @@
+use super::{
+    build_menus::BuildMenuScreen,
+    enemies::{definitions::EnemyDefinitions, Enemy},
+    statistics::{EnemyKillCount, GrenadesFired, LaserShotsFired, RocketsFired, TowerUpgrades},
+    systems::{
+        game_over::GameOverTimer,
+        wave::{Wave, WaveState},
+    },
+    Game, GameScreen,
+};
+use crate::{
+    board::{visualisation::TILE_SIZE, Board},
+    utils::{
+        buildings::{
+            factory::{spawn_factory, Factory},
+            power_plant::{spawn_power_plant, PowerPlant},
+        },
+        file_name_of,
+        speed::Speed,
+        status_effects::StatusEffects,
+        towers::{draw_tower, Tower},
+        GameRng, IngameTime,
+    },
+};
+use bevy::{ecs::system::SystemParam, prelude::*, window::WindowCloseRequested};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fs::{create_dir_all, read_dir, read_to_string, remove_file, File},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+pub const SAVES_DIR: &str = "./saves/";
+
+// Increase on every change of the save format, older saves get rejected
+pub const SAVE_VERSION: u32 = 5;
+
+#[derive(Message)]
+pub struct SaveGameMessage;
+
+// Everything needed to resume a running match.
+// Shots in flight and explosions are not saved, they are gone after loading.
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub(crate) struct SaveGame {
+    version: u32,
+    pub game: Game,
+    pub board: Board,
+    towers: Vec<Tower>,
+    power_plants: Vec<PowerPlant>,
+    factories: Vec<Factory>,
+    enemies: Vec<(Enemy, Speed, StatusEffects)>,
+    wave: Option<Wave>,
+    ingame_time: Duration,
+    rng: GameRng,
+    kill_count: EnemyKillCount,
+    laser_count: LaserShotsFired,
+    rocket_count: RocketsFired,
+    grenade_count: GrenadesFired,
+    upgrade_count: TowerUpgrades,
+    go_timer: GameOverTimer,
+}
+
+#[derive(Deserialize)]
+struct SaveVersion {
+    version: u32,
+}
+
+impl SaveGame {
+    pub fn from_file(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let json = read_to_string(path)?;
+        let SaveVersion { version } = serde_json::from_str(&json)?;
+        if version != SAVE_VERSION {
+            return Err(format!(
+                "Save '{}' has version {version}, but only version {SAVE_VERSION} is supported",
+                path.display()
+            )
+            .into());
+        }
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn to_file(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir)?;
+        }
+        let mut output = File::create(path)?;
+        write!(output, "{}", serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+pub fn save_path(map_name: &str) -> PathBuf {
+    Path::new(SAVES_DIR).join(format!("{}_save.json", file_name_of(map_name)))
+}
+
+// Most recently written save, if there is any
+pub fn latest_save_path() -> Option<PathBuf> {
+    read_dir(SAVES_DIR)
+        .ok()?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .filter(|(_, path)| path.extension().is_some_and(|ext| ext == "json"))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+#[derive(SystemParam)]
+pub(super) struct SaveGameParams<'w, 's> {
+    game: Res<'w, Game>,
+    board: Res<'w, Board>,
+    wave: Option<Res<'w, Wave>>,
+    time: Res<'w, IngameTime>,
+    rng: Res<'w, GameRng>,
+    kill_count: Res<'w, EnemyKillCount>,
+    laser_count: Res<'w, LaserShotsFired>,
+    rocket_count: Res<'w, RocketsFired>,
+    grenade_count: Res<'w, GrenadesFired>,
+    upgrade_count: Res<'w, TowerUpgrades>,
+    go_timer: Res<'w, GameOverTimer>,
+    q_towers: Query<'w, 's, &'static Tower, Without<BuildMenuScreen>>,
+    q_power_plants: Query<'w, 's, &'static PowerPlant, Without<BuildMenuScreen>>,
+    q_factories: Query<'w, 's, &'static Factory, Without<BuildMenuScreen>>,
+    q_enemies: Query<'w, 's, (&'static Enemy, &'static Speed, &'static StatusEffects)>,
+}
+
+impl SaveGameParams<'_, '_> {
+    pub fn to_save_game(&self) -> SaveGame {
+        let is_wave_running = !self.game.is_between_waves();
+        SaveGame {
+            version: SAVE_VERSION,
+            game: self.game.clone(),
+            board: self.board.clone(),
+            towers: self.q_towers.iter().cloned().collect(),
+            power_plants: self.q_power_plants.iter().cloned().collect(),
+            factories: self.q_factories.iter().cloned().collect(),
+            enemies: self
+                .q_enemies
+                .iter()
+                .map(|(enemy, speed, effects)| (enemy.clone(), speed.clone(), effects.clone()))
+                .collect(),
+            wave: self
+                .wave
+                .as_ref()
+                .filter(|_| is_wave_running)
+                .map(|wave| (**wave).clone()),
+            ingame_time: self.time.elapsed(),
+            rng: self.rng.clone(),
+            kill_count: *self.kill_count,
+            laser_count: *self.laser_count,
+            rocket_count: *self.rocket_count,
+            grenade_count: *self.grenade_count,
+            upgrade_count: *self.upgrade_count,
+            go_timer: *self.go_timer,
+        }
+    }
+
+    fn save(&self) {
+        let path = save_path(&self.board.name);
+        if let Err(err) = self.to_save_game().to_file(&path) {
+            error!("Failed to save game to '{}': {err}", path.display());
+        }
+    }
+}
+
+pub(super) fn on_save_game(mut events: MessageReader<SaveGameMessage>, params: SaveGameParams) {
+    if events.read().count() > 0 {
+        params.save();
+    }
+}
+
+// Closing the window should not lose the match
+pub(super) fn save_on_window_close(
+    mut events: MessageReader<WindowCloseRequested>,
+    params: SaveGameParams,
+) {
+    if events.read().count() > 0 {
+        params.save();
+    }
+}
+
+// A finished match must not be continued
+pub(super) fn delete_save_on_game_over(board: Res<Board>) {
+    let path = save_path(&board.name);
+    if let Err(err) = remove_file(&path) {
+        if err.kind() != ErrorKind::NotFound {
+            error!("Failed to delete save '{}': {err}", path.display());
+        }
+    }
+}
+
+// Runs after the game setup and replaces its fresh state with the saved one
+pub(super) fn restore_save_game(
+    mut cmds: Commands,
+    mut set_wave_state: ResMut<NextState<WaveState>>,
+    save: Option<Res<SaveGame>>,
+    definitions: Res<EnemyDefinitions>,
+) {
+    let Some(save) = save else { return };
+    cmds.insert_resource(IngameTime::from_elapsed(save.ingame_time));
+    cmds.insert_resource(save.rng.clone());
+    cmds.insert_resource(save.kill_count);
+    cmds.insert_resource(save.laser_count);
+    cmds.insert_resource(save.rocket_count);
+    cmds.insert_resource(save.grenade_count);
+    cmds.insert_resource(save.upgrade_count);
+    cmds.insert_resource(save.go_timer);
+    if let Some(wave) = &save.wave {
+        cmds.insert_resource(wave.clone());
+        set_wave_state.set(WaveState::Running);
+    }
+
+    for tower in save.towers.iter() {
+        let mut tower = tower.clone();
+        tower.values_mut().target_lock = None;
+        draw_tower::<GameScreen>(&mut cmds, tower.values().pos, &tower);
+    }
+    for power_plant in save.power_plants.iter() {
+        spawn_power_plant::<GameScreen>(&mut cmds, power_plant.clone(), TILE_SIZE);
+    }
+    for factory in save.factories.iter() {
+        spawn_factory::<GameScreen>(&mut cmds, factory.clone(), TILE_SIZE);
+    }
+    for (enemy, speed, effects) in save.enemies.iter() {
+        let entity = enemy.clone().spawn(&mut cmds, &definitions);
+        cmds.entity(entity).insert((speed.clone(), effects.clone()));
+    }
+    cmds.remove_resource::<SaveGame>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headless::{headless_app, HeadlessConfig};
+    use crate::utils::{
+        status_effects::{StatusEffect, StatusEffectKind},
+        IngameTimestamp,
+    };
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn app() -> App {
+        let args = ["--map", "0test", "--seed", "11"].map(String::from);
+        headless_app(HeadlessConfig::from_args(&args).unwrap()).unwrap()
+    }
+
+    fn save_game(app: &mut App) -> SaveGame {
+        let world = app.world_mut();
+        world
+            .run_system_once(|params: SaveGameParams| params.to_save_game())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_save_path_stays_in_saves_dir() {
+        let path = save_path("../maps/Snake/..");
+        assert_eq!(path.parent(), Some(Path::new(SAVES_DIR)));
+        assert_eq!(path, save_path("___maps_Snake___"));
+        assert_eq!(
+            save_path("Fat Cat"),
+            Path::new(SAVES_DIR).join("Fat Cat_save.json")
+        );
+    }
+
+    #[test]
+    fn test_save_and_restore_match() {
+        let mut app_1 = app();
+        for _ in 0..1_500 {
+            app_1.update();
+        }
+        let countdown = GameOverTimer::Active(IngameTimestamp(1_000.));
+        app_1.insert_resource(countdown);
+        let world = app_1.world_mut();
+        for mut effects in world.query::<&mut StatusEffects>().iter_mut(world) {
+            effects.add(StatusEffect {
+                kind: StatusEffectKind::Slow(0.5),
+                until: IngameTimestamp(1_000.),
+            });
+        }
+        let save = save_game(&mut app_1);
+        assert_eq!(save.go_timer, countdown);
+        assert!(!save.enemies.is_empty());
+
+        let json = serde_json::to_string(&save).unwrap();
+        let loaded: SaveGame = serde_json::from_str(&json).unwrap();
+        let mut app_2 = app();
+        app_2.insert_resource(loaded.game.clone());
+        app_2.insert_resource(loaded);
+        app_2.update();
+
+        let restored = save_game(&mut app_2);
+        assert_eq!(restored.enemies.len(), save.enemies.len());
+        assert_eq!(restored.game.wave_no(), save.game.wave_no());
+        assert_eq!(restored.kill_count.0, save.kill_count.0);
+        assert!(restored.ingame_time >= save.ingame_time);
+        assert_eq!(restored.go_timer, save.go_timer);
+        assert!(restored
+            .enemies
+            .iter()
+            .all(|(_, _, effects)| *effects != StatusEffects::default()));
+    }
+}
@@ -1,10 +1,17 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct EnemyKillCount(pub u64);
 
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct LaserShotsFired(pub u64);
 
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct RocketsFired(pub u64);
+
+#[derive(Resource, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct GrenadesFired(pub u64);
+
+#[derive(Resource, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct TowerUpgrades(pub u64);
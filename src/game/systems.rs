@@ -5,19 +5,27 @@ use self::{
     death::death_system,
     enemy::{enemy_collision_add_system, enemy_walk_system},
     explosions::explosion_system,
-    game_over::{end_game, game_over_screen, game_over_system, game_over_timer_system},
+    game_over::{
+        end_game, game_over_countdown_system, game_over_screen, game_over_system,
+        game_over_timer_system,
+    },
     health_bar::health_bar_system,
     pause::{pause_menu, reset_pause_menu, PauseMenu},
     resource::{resource_animation_system, resource_symbol_fade_system, resource_text_fade_system},
     resource_bar::resource_bar_system,
+    sell_menu::sell_menu,
     speed::acceleration_system,
-    tower::{tower_overheat_system, tower_rotation_system, tower_target_system},
-    wave::{wave_spawn_system, wave_system, WaveState},
+    status_effects::{status_effect_indicator_system, status_effect_system},
+    tower::{
+        tower_level_system, tower_overheat_system, tower_rotation_system, tower_target_system,
+    },
+    tower_panel::tower_panel,
+    wave::{wave_spawn_system, wave_system, Wave, WaveState},
 };
 use bevy::prelude::*;
 use bevy_egui::EguiPrimaryContextPass;
 
-use super::{actions::Labels, controls::hovered_tile, IngameState};
+use super::{actions::Labels, controls::hovered_tile, is_simulation_running, IngameState};
 
 pub mod base;
 pub mod building;
@@ -30,9 +38,12 @@ pub mod health_bar;
 pub mod pause;
 pub mod resource;
 pub mod resource_bar;
+pub mod sell_menu;
 pub mod shot;
 pub mod speed;
+pub mod status_effects;
 pub mod tower;
+pub mod tower_panel;
 pub mod wave;
 
 pub struct GameSystems;
@@ -42,51 +53,58 @@ impl Plugin for GameSystems {
         app.init_state::<WaveState>()
             .init_resource::<PauseMenu>()
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
                     wave_spawn_system,
+                    wave_system.run_if(resource_exists::<Wave>),
+                    status_effect_system,
                     acceleration_system,
                     enemy_walk_system,
+                    enemy_collision_add_system,
+                    enemy_collision_remove_system,
                     power_plant_system,
                     factory_system,
-                    resource_animation_system,
-                    resource_text_fade_system,
-                    resource_symbol_fade_system,
                     tower_target_system,
-                    tower_rotation_system,
-                    tower_overheat_system,
-                    health_bar_system,
-                    resource_bar_system,
-                    base_system,
+                    (
+                        shot::damage_per_time::damage_system,
+                        shot::damage_per_time::despawn_system,
+                        shot::damage_in_radius_enemy_locked::fly_system,
+                        shot::damage_in_radius_enemy_locked::damage_and_despawn_system,
+                        shot::slow_in_radius::pulse_system,
+                        shot::slow_in_radius::despawn_system,
+                        shot::ballistic::fly_system,
+                        shot::ballistic::explode_system,
+                    )
+                        .chain(),
                     explosion_system,
                     death_system,
                     game_over_timer_system,
-                    hovered_tile,
                     game_over_system,
                 )
-                    .run_if(in_state(IngameState::Running)),
-            )
-            .add_systems(
-                Update,
-                enemy_collision_add_system
-                    .before(Labels::CollisionAdd)
-                    .run_if(in_state(IngameState::Running)),
-            )
-            .add_systems(
-                Update,
-                enemy_collision_remove_system
-                    .before(Labels::CollisionRemove)
-                    .run_if(in_state(IngameState::Running)),
+                    .chain()
+                    .in_set(Labels::Systems)
+                    .before(Labels::Actions)
+                    .run_if(is_simulation_running),
             )
             .add_systems(
                 Update,
                 (
-                    shot::damage_per_time::damage_system,
+                    resource_animation_system,
+                    resource_text_fade_system,
+                    resource_symbol_fade_system,
+                    tower_rotation_system,
+                    tower_overheat_system,
+                    tower_level_system,
+                    health_bar_system,
+                    status_effect_indicator_system,
+                    resource_bar_system,
+                    base_system,
+                    game_over_countdown_system,
+                    hovered_tile,
                     shot::damage_per_time::visual_system,
-                    shot::damage_per_time::despawn_system,
-                    shot::damage_in_radius_enemy_locked::fly_system,
                     shot::damage_in_radius_enemy_locked::visual_system,
-                    shot::damage_in_radius_enemy_locked::damage_and_despawn_system,
+                    shot::slow_in_radius::visual_system,
+                    shot::ballistic::visual_system,
                 )
                     .run_if(in_state(IngameState::Running)),
             )
@@ -95,16 +113,14 @@ impl Plugin for GameSystems {
                 EguiPrimaryContextPass,
                 (game_over_screen).run_if(in_state(IngameState::GameOver)),
             )
-            .add_systems(OnEnter(IngameState::Pause), reset_pause_menu)
             .add_systems(
                 EguiPrimaryContextPass,
-                (pause_menu).run_if(in_state(IngameState::Pause)),
+                (tower_panel, sell_menu).run_if(in_state(IngameState::Running)),
             )
+            .add_systems(OnEnter(IngameState::Pause), reset_pause_menu)
             .add_systems(
-                Update,
-                (wave_system)
-                    .before(Labels::Actions)
-                    .run_if(in_state(WaveState::Running).and(in_state(IngameState::Running))),
+                EguiPrimaryContextPass,
+                (pause_menu).run_if(in_state(IngameState::Pause)),
             );
     }
 }
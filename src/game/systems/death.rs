@@ -1,7 +1,6 @@
-use crate::balance::ENEMY_KILL_MATERIAL_REWARD_DIVISOR;
 use crate::game::{
     actions::{explosions::ExplosionMessage, resources::ResourcesMessage},
-    enemies::Enemy,
+    enemies::{definitions::EnemyDefinitions, Enemy},
     statistics::EnemyKillCount,
 };
 use bevy::prelude::*;
@@ -12,14 +11,15 @@ pub fn death_system(
     mut res_actions: MessageWriter<ResourcesMessage>,
     mut kill_count: ResMut<EnemyKillCount>,
     q_enemies: Query<(Entity, &Enemy)>,
+    definitions: Res<EnemyDefinitions>,
 ) {
     for (entity, enemy) in q_enemies.iter() {
         if enemy.health <= 0. {
             expl_actions.write(ExplosionMessage::death(enemy));
-            res_actions.write(ResourcesMessage::Materials(
-                enemy.health_max / ENEMY_KILL_MATERIAL_REWARD_DIVISOR,
-                enemy.pos,
-            ));
+            let reward = definitions
+                .get(enemy.enemy_type())
+                .map_or(0., |definition| definition.reward);
+            res_actions.write(ResourcesMessage::Materials(reward, enemy.pos));
             cmds.entity(entity).try_despawn();
             kill_count.0 += 1;
         }
@@ -1,12 +1,12 @@
 use crate::{
-    balance::ENEMY_BASE_DAMAGE_MULTIPLIER,
-    board::{step::BoardDirection, BoardCache},
+    board::{step::BoardDirection, Board, BoardCache, Tile},
     game::{
         actions::{collision::EnemyCollisionAddMessage, resources::ResourcesMessage},
-        enemies::Enemy,
+        enemies::{definitions::EnemyDefinitions, Enemy},
     },
     utils::{
         collision::{Collision, Collisions},
+        interpolation::TickTranslation,
         speed::Speed,
         IngameTime, Vec2Board,
     },
@@ -14,27 +14,42 @@ use crate::{
 use bevy::prelude::*;
 
 type QEnemies<'w, 's, 'a> =
-    Query<'w, 's, (Entity, &'a mut Enemy, &'a mut Transform, &'a Speed), With<Enemy>>;
+    Query<'w, 's, (Entity, &'a mut Enemy, &'a mut TickTranslation, &'a Speed), With<Enemy>>;
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn enemy_walk_system(
     mut cmds: Commands,
     mut res_actions: MessageWriter<ResourcesMessage>,
     mut q_enemies: QEnemies,
+    board: Res<Board>,
     board_cache: Res<BoardCache>,
     time: Res<IngameTime>,
     collisions: Res<Collisions>,
+    definitions: Res<EnemyDefinitions>,
 ) {
     let dur = time.delta();
     q_enemies
         .iter_mut()
-        .for_each(|(entity, mut enemy, mut transform, speed)| {
+        .for_each(|(entity, mut enemy, mut translation, speed)| {
             if !collisions
                 .iter()
                 .any(|coll| coll.enemy_behind == entity && coll.is_critical)
             {
-                match enemy.walk_until_end(dur, speed.current, &board_cache) {
-                    true => enemy_reached_base(&mut cmds, &mut res_actions, &enemy, entity),
-                    false => transform.translation = enemy.pos.to_scaled_vec3(1.),
+                let terrain_factor = match enemy.is_flying() {
+                    true => 1.,
+                    false => board
+                        .get_tile(&enemy.pos.as_uvec2())
+                        .map_or(1., Tile::speed_factor),
+                };
+                match enemy.walk_until_end(dur, speed.current * terrain_factor, &board_cache) {
+                    true => enemy_reached_base(
+                        &mut cmds,
+                        &mut res_actions,
+                        &enemy,
+                        entity,
+                        &definitions,
+                    ),
+                    false => translation.current = enemy.pos.to_scaled_vec3(enemy.z()),
                 }
                 set_enemy_spawn_line_flag(&mut enemy, &board_cache);
             }
@@ -45,16 +60,19 @@ pub(super) fn enemy_collision_add_system(
     mut add_ev: MessageWriter<EnemyCollisionAddMessage>,
     mut collisions: ResMut<Collisions>,
     q_enemies: Query<(Entity, &Enemy)>,
+    board_cache: Res<BoardCache>,
 ) {
-    q_enemies.iter().for_each(|(entity, enemy)| {
-        q_enemies.iter().for_each(|(other_entity, other_enemy)| {
+    // Flying enemies don't queue up on the road
+    let q_ground_enemies = || q_enemies.iter().filter(|(_, enemy)| !enemy.is_flying());
+    q_ground_enemies().for_each(|(entity, enemy)| {
+        q_ground_enemies().for_each(|(other_entity, other_enemy)| {
             if entity != other_entity {
                 let distance = enemy.pos.distance(*other_enemy.pos);
                 if distance <= enemy.break_radius + other_enemy.break_radius
                     && !is_already_found(entity, other_entity, &collisions)
                 {
                     let is_critical = distance <= enemy.size_radius + other_enemy.size_radius;
-                    collisions.push(if enemy.is_behind_of(other_enemy) {
+                    collisions.push(if enemy.is_behind_of(other_enemy, &board_cache) {
                         Collision::new(other_entity, entity, is_critical)
                     } else {
                         Collision::new(entity, other_entity, is_critical)
@@ -78,8 +96,13 @@ fn enemy_reached_base(
     res_actions: &mut MessageWriter<ResourcesMessage>,
     enemy: &Enemy,
     entity: Entity,
+    definitions: &EnemyDefinitions,
 ) {
-    let damage = (-enemy.health * ENEMY_BASE_DAMAGE_MULTIPLIER).round();
+    // Wounded enemies do less damage
+    let base_damage = definitions
+        .get(enemy.enemy_type())
+        .map_or(0., |definition| definition.base_damage);
+    let damage = (-base_damage * enemy.health_as_percent()).round();
     res_actions.write(ResourcesMessage::Energy(damage, enemy.pos));
     res_actions.write(ResourcesMessage::Materials(damage, enemy.pos));
     cmds.entity(entity).try_despawn();
@@ -87,8 +110,11 @@ fn enemy_reached_base(
 
 fn set_enemy_spawn_line_flag(enemy: &mut Enemy, board_cache: &BoardCache) {
     if enemy.is_in_spawn {
-        let start = Vec2Board::from(board_cache.road_start_pos.unwrap());
-        let step = board_cache.road_path.first().unwrap();
+        let start = Vec2Board::from(board_cache.road_entrances[enemy.entrance].pos);
+        let step = board_cache
+            .road_path(enemy.entrance, enemy.route)
+            .first()
+            .unwrap();
         enemy.is_in_spawn = is_in_spawn(start, enemy.pos, enemy.size_radius, step.direction);
     }
 }
@@ -1,13 +1,15 @@
 use crate::{
     game::enemies::Enemy,
-    utils::{explosions::Explosion, IngameTime},
+    utils::{explosions::Explosion, status_effects::StatusEffects, IngameTime},
 };
 use bevy::prelude::*;
 
+type EnemiesQuery<'w, 's, 'a> = Query<'w, 's, (&'a mut Enemy, &'a StatusEffects)>;
+
 pub fn explosion_system(
     mut cmds: Commands,
     mut q_explosions: Query<(Entity, &mut Transform, &mut Explosion)>,
-    mut q_enemies: Query<&mut Enemy>,
+    mut q_enemies: EnemiesQuery,
     time: Res<IngameTime>,
 ) {
     for (entity, mut transform, mut expl) in q_explosions.iter_mut() {
@@ -22,10 +24,11 @@ pub fn explosion_system(
     }
 }
 
-fn damage_enemies_in_range(q_enemies: &mut Query<&mut Enemy>, expl: &Explosion) {
-    for mut enemy in q_enemies.iter_mut() {
-        if enemy.is_in_range(expl.pos, expl.target_radius) {
-            enemy.health -= expl.damage;
+// Explosions are on the ground and miss flying enemies
+fn damage_enemies_in_range(q_enemies: &mut EnemiesQuery, expl: &Explosion) {
+    for (mut enemy, effects) in q_enemies.iter_mut() {
+        if !enemy.is_flying() && enemy.is_in_range(expl.pos, expl.target_radius) {
+            enemy.take_damage(&expl.damage.scaled(effects.damage_multiplier()));
         }
     }
 }
@@ -2,7 +2,8 @@ use super::wave::WaveState;
 use crate::{
     board::visualisation::{BoardRoadEndMark, GameOverCountDownText},
     game::{
-        statistics::{EnemyKillCount, LaserShotsFired, RocketsFired},
+        statistics::{EnemyKillCount, GrenadesFired, LaserShotsFired, RocketsFired, TowerUpgrades},
+        test_play::TestPlay,
         Game, IngameState, GAME_OVER_COUNTDOWN_TIME,
     },
     utils::{GameState, IngameTime, IngameTimestamp},
@@ -13,45 +14,58 @@ use bevy_egui::{
     EguiContexts,
 };
 use bevy_prototype_lyon::entity::Shape;
+use serde::{Deserialize, Serialize};
 
-#[derive(Resource)]
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub(in crate::game) enum GameOverTimer {
     Active(IngameTimestamp),
+    #[default]
     Inactive,
 }
 
-impl Default for GameOverTimer {
-    fn default() -> Self {
-        Self::Inactive
+impl GameOverTimer {
+    pub fn is_over(&self, now: IngameTimestamp) -> bool {
+        matches!(self, GameOverTimer::Active(time_game_over) if *time_game_over <= now)
     }
 }
 
 pub(super) fn game_over_timer_system(
     mut go_timer: ResMut<GameOverTimer>,
-    mut q_go_text: Query<(&mut Text2d, &mut Visibility), With<GameOverCountDownText>>,
-    q_base: Query<&mut Shape, With<BoardRoadEndMark>>,
     time: Res<IngameTime>,
     game: Res<Game>,
 ) {
     if game.energy < 0. || game.materials < 0. {
-        let Ok(mut text) = q_go_text.single_mut() else {
-            return;
-        };
-        if let GameOverTimer::Active(game_over_time) = go_timer.as_ref() {
-            set_base_color(q_base, time.now());
-            text.0 .0 = format!("{}", *(*game_over_time - *time.now()) as u32);
-        } else {
+        if let GameOverTimer::Inactive = *go_timer {
             *go_timer = GameOverTimer::Active(time.now() + GAME_OVER_COUNTDOWN_TIME);
-            *text.1 = Visibility::Visible;
         }
     } else if let GameOverTimer::Active(_) = *go_timer {
         *go_timer = GameOverTimer::Inactive;
-        set_base_color(q_base, IngameTimestamp(0.5));
-        let Ok(mut text) = q_go_text.single_mut() else {
-            return;
-        };
-        text.0 .0 = format!("{}", GAME_OVER_COUNTDOWN_TIME.as_secs());
-        *text.1 = Visibility::Hidden;
+    }
+}
+
+pub(super) fn game_over_countdown_system(
+    mut q_go_text: Query<(&mut Text2d, &mut Visibility), With<GameOverCountDownText>>,
+    q_base: Query<&mut Shape, With<BoardRoadEndMark>>,
+    go_timer: Res<GameOverTimer>,
+    time: Res<IngameTime>,
+) {
+    if !go_timer.is_changed() && matches!(*go_timer, GameOverTimer::Inactive) {
+        return;
+    }
+    let Ok(mut text) = q_go_text.single_mut() else {
+        return;
+    };
+    match go_timer.as_ref() {
+        GameOverTimer::Active(game_over_time) => {
+            set_base_color(q_base, time.now());
+            text.0 .0 = format!("{}", *(*game_over_time - *time.now()) as u32);
+            *text.1 = Visibility::Visible;
+        }
+        GameOverTimer::Inactive => {
+            set_base_color(q_base, IngameTimestamp(0.5));
+            text.0 .0 = format!("{}", GAME_OVER_COUNTDOWN_TIME.as_secs());
+            *text.1 = Visibility::Hidden;
+        }
     }
 }
 
@@ -69,10 +83,8 @@ pub(super) fn game_over_system(
     go_timer: Res<GameOverTimer>,
     time: Res<IngameTime>,
 ) {
-    if let GameOverTimer::Active(time_game_over) = go_timer.as_ref() {
-        if *time_game_over <= time.now() {
-            set_ingame_state.set(IngameState::GameOver);
-        }
+    if go_timer.is_over(time.now()) {
+        set_ingame_state.set(IngameState::GameOver);
     }
 }
 
@@ -96,7 +108,10 @@ pub(super) fn game_over_screen(
     kill_count: Res<EnemyKillCount>,
     laser_count: Res<LaserShotsFired>,
     rocket_count: Res<RocketsFired>,
+    grenade_count: Res<GrenadesFired>,
+    upgrade_count: Res<TowerUpgrades>,
     time: Res<IngameTime>,
+    test_play: Option<Res<TestPlay>>,
 ) {
     let Ok(ctx) = egui_ctx.ctx_mut() else { return };
     CentralPanel::default().show(ctx, |ui| {
@@ -119,11 +134,14 @@ pub(super) fn game_over_screen(
                             let time = time.elapsed_secs_f64();
                             add_stat_row("Ingame Time", &format_secs_time(time), ui);
                             add_stat_row("Wave", &format!("{}", game.wave_no), ui);
+                            add_stat_row("Seed", &format!("{}", game.seed), ui);
                             add_stat_row("Energy", &format!("{}", game.energy), ui);
                             add_stat_row("Materials", &format!("{}", game.materials), ui);
                             add_stat_row("Enemies Killed", &format!("{}", kill_count.0), ui);
                             add_stat_row("Laser Shots Fired", &format!("{}", laser_count.0), ui);
                             add_stat_row("Rockets Fired", &format!("{}", rocket_count.0), ui);
+                            add_stat_row("Grenades Fired", &format!("{}", grenade_count.0), ui);
+                            add_stat_row("Tower Upgrades", &format!("{}", upgrade_count.0), ui);
                         });
                 });
             });
@@ -142,15 +160,19 @@ pub(super) fn game_over_screen(
             })
             .show_inside(ui, |ui| {
                 ui.vertical_centered(|ui| {
+                    let back_text = match test_play.is_some() {
+                        true => "Back To Editor",
+                        false => "Back To Main Menu",
+                    };
                     if ui
                         .add_sized(
                             [400., 60.],
-                            bevy_egui::egui::widgets::Button::new("Back To Main Menu"),
+                            bevy_egui::egui::widgets::Button::new(back_text),
                         )
                         .clicked()
                     {
                         set_ingame_state.set(IngameState::None);
-                        set_game_state.set(GameState::Menu);
+                        set_game_state.set(TestPlay::exit_state(test_play.as_deref()));
                     }
                 });
             });
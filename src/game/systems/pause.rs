@@ -2,19 +2,24 @@ use crate::{
     game::{
         actions::GameActionMessage,
         actions::GameActionMessage::{BackToMainMenu, Continue},
+        enemies::definitions::EnemyDefinitions,
+        save::SaveGameMessage,
+        test_play::TestPlay,
     },
     menu_panels::{controls_content, settings_content},
     user::Settings,
+    utils::damage::DamageType,
     TITLE,
 };
 use bevy::prelude::*;
 use bevy_egui::{
     egui::{
-        self, Align, Button, CentralPanel, Color32, Frame, Label, Layout, RichText, ScrollArea,
-        Stroke,
+        self, Align, Button, CentralPanel, Color32, Frame, Grid, Label, Layout, RichText,
+        ScrollArea, Stroke,
     },
     EguiContexts,
 };
+use strum::IntoEnumIterator;
 
 const PAUSE_MENU_WIDTH: f32 = 360.;
 const PAUSE_PANEL_WIDTH: f32 = 720.;
@@ -23,6 +28,7 @@ const PAUSE_BUTTON_HEIGHT: f32 = 58.;
 #[derive(Resource, Default)]
 pub(super) struct PauseMenu {
     screen: PauseMenuScreen,
+    is_saved: bool,
 }
 
 #[derive(Default)]
@@ -31,10 +37,12 @@ enum PauseMenuScreen {
     Main,
     Settings,
     Controls,
+    Codex,
 }
 
 pub(super) fn reset_pause_menu(mut pause_menu: ResMut<PauseMenu>) {
     pause_menu.screen = PauseMenuScreen::Main;
+    pause_menu.is_saved = false;
 }
 
 pub(super) fn pause_menu(
@@ -42,6 +50,9 @@ pub(super) fn pause_menu(
     mut pause_menu: ResMut<PauseMenu>,
     mut settings: ResMut<Settings>,
     mut actions: MessageWriter<GameActionMessage>,
+    mut save_ev: MessageWriter<SaveGameMessage>,
+    test_play: Option<Res<TestPlay>>,
+    definitions: Res<EnemyDefinitions>,
 ) {
     let Ok(ctx) = egui_ctx.ctx_mut() else { return };
     CentralPanel::default()
@@ -50,9 +61,19 @@ pub(super) fn pause_menu(
             ui.with_layout(Layout::top_down(Align::Center), |ui| {
                 ui.add_space((ui.available_height() * 0.14).max(32.));
                 match pause_menu.screen {
-                    PauseMenuScreen::Main => pause_card(ui, &mut pause_menu, &mut actions),
+                    PauseMenuScreen::Main => {
+                        let is_test_play = test_play.is_some();
+                        pause_card(
+                            ui,
+                            &mut pause_menu,
+                            &mut actions,
+                            &mut save_ev,
+                            is_test_play,
+                        )
+                    }
                     PauseMenuScreen::Settings => settings_card(ui, &mut pause_menu, &mut settings),
                     PauseMenuScreen::Controls => controls_card(ui, &mut pause_menu),
+                    PauseMenuScreen::Codex => codex_card(ui, &mut pause_menu, &definitions),
                 }
             });
         });
@@ -73,6 +94,8 @@ fn pause_card(
     ui: &mut egui::Ui,
     pause_menu: &mut PauseMenu,
     actions: &mut MessageWriter<GameActionMessage>,
+    save_ev: &mut MessageWriter<SaveGameMessage>,
+    is_test_play: bool,
 ) {
     menu_frame().show(ui, |ui| {
         ui.set_width(PAUSE_MENU_WIDTH);
@@ -86,6 +109,18 @@ fn pause_card(
                 actions.write(Continue);
             }
 
+            // Test plays are thrown away, the map lives on in the editor
+            if !is_test_play {
+                let save_text = match pause_menu.is_saved {
+                    true => "Saved",
+                    false => "Save",
+                };
+                if pause_button(ui, save_text).clicked() {
+                    save_ev.write(SaveGameMessage);
+                    pause_menu.is_saved = true;
+                }
+            }
+
             if pause_button(ui, "Settings").clicked() {
                 pause_menu.screen = PauseMenuScreen::Settings;
             }
@@ -94,7 +129,15 @@ fn pause_card(
                 pause_menu.screen = PauseMenuScreen::Controls;
             }
 
-            if pause_button(ui, "Back to main").clicked() {
+            if pause_button(ui, "Enemy codex").clicked() {
+                pause_menu.screen = PauseMenuScreen::Codex;
+            }
+
+            let back_text = match is_test_play {
+                true => "Back to editor",
+                false => "Back to main",
+            };
+            if pause_button(ui, back_text).clicked() {
                 actions.write(BackToMainMenu);
             }
         });
@@ -132,6 +175,58 @@ fn controls_card(ui: &mut egui::Ui, pause_menu: &mut PauseMenu) {
     });
 }
 
+fn codex_card(ui: &mut egui::Ui, pause_menu: &mut PauseMenu, definitions: &EnemyDefinitions) {
+    menu_frame().show(ui, |ui| {
+        ui.set_width(PAUSE_PANEL_WIDTH);
+        panel_header(ui, "Enemy codex", pause_menu);
+        ui.add_space(16.);
+        ui.label(
+            RichText::new("Resistances reduce damage of their type, negative ones increase it")
+                .small()
+                .color(Color32::from_gray(190)),
+        );
+        ui.add_space(8.);
+
+        ScrollArea::vertical()
+            .max_height((ui.available_height() - 20.).max(220.))
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                Grid::new("enemy_codex").striped(true).show(ui, |ui| {
+                    for header in ["Enemy", "Health", "Speed", "Moves"] {
+                        ui.strong(header);
+                    }
+                    for kind in DamageType::iter() {
+                        ui.strong(kind.to_string());
+                    }
+                    ui.end_row();
+
+                    for definition in definitions.iter() {
+                        ui.label(&definition.name);
+                        ui.label(format!("{}", definition.health));
+                        ui.label(format!("{}", definition.speed));
+                        ui.label(match definition.is_flying {
+                            true => "Flying",
+                            false => "Road",
+                        });
+                        for kind in DamageType::iter() {
+                            ui.label(resistance_text(definition.resistances.get(kind)));
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+    });
+}
+
+fn resistance_text(resistance: f32) -> RichText {
+    let text = RichText::new(format!("{:+.0}%", resistance * 100.));
+    match resistance {
+        0. => RichText::new("-").color(Color32::from_gray(150)),
+        _ if resistance > 0. => text.color(Color32::LIGHT_GREEN),
+        _ => text.color(Color32::LIGHT_RED),
+    }
+}
+
 fn panel_header(ui: &mut egui::Ui, title: &str, pause_menu: &mut PauseMenu) {
     ui.horizontal(|ui| {
         if ui.button("Back").clicked() {
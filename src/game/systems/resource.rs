@@ -9,10 +9,11 @@ use bevy_prototype_lyon::entity::Shape;
 pub fn resource_animation_system(
     mut cmds: Commands,
     mut q_anims: Query<(Entity, &mut Transform, &ResourceAnimation)>,
-    time: Res<IngameTime>,
+    ingame_time: Res<IngameTime>,
+    time: Res<Time>,
 ) {
     let delta = time.delta_secs();
-    let now = time.now();
+    let now = ingame_time.now();
     for (entity, mut transform, anim) in q_anims.iter_mut() {
         if now >= anim.die_time {
             cmds.entity(entity).try_despawn();
@@ -24,7 +25,7 @@ pub fn resource_animation_system(
 
 pub fn resource_text_fade_system(
     mut q_text_colors: Query<&mut TextColor, With<ResourceTextFade>>,
-    time: Res<IngameTime>,
+    time: Res<Time>,
 ) {
     let delta = time.delta_secs();
     for mut color in q_text_colors.iter_mut() {
@@ -35,7 +36,7 @@ pub fn resource_text_fade_system(
 
 pub fn resource_symbol_fade_system(
     mut q_symbols: Query<&mut Shape, With<ResourceSymbolFade>>,
-    time: Res<IngameTime>,
+    time: Res<Time>,
 ) {
     let delta = time.delta_secs();
     for mut shape in q_symbols.iter_mut() {
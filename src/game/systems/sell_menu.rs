@@ -0,0 +1,70 @@
+use crate::{
+    game::actions::sell::{find_sellable, QuerySellables, SellMessage},
+    utils::IngameTime,
+};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Pos2},
+    EguiContexts,
+};
+
+// Right clicked tower or building, asks before selling it
+#[derive(Resource, Default)]
+pub(in crate::game) struct SellMenu {
+    pub tile_pos: Option<UVec2>,
+    // Opens at the cursor and stays there
+    screen_pos: Option<Pos2>,
+}
+
+impl SellMenu {
+    pub fn open(&mut self, tile_pos: Option<UVec2>) {
+        self.tile_pos = tile_pos;
+        self.screen_pos = None;
+    }
+}
+
+pub(super) fn sell_menu(
+    mut egui_ctx: EguiContexts,
+    mut menu: ResMut<SellMenu>,
+    mut sell_ev: MessageWriter<SellMessage>,
+    q_sellables: QuerySellables,
+    time: Res<IngameTime>,
+) {
+    let Some(tile_pos) = menu.tile_pos else {
+        return;
+    };
+    let Some(sellable) = find_sellable(&q_sellables, tile_pos, time.now()) else {
+        menu.open(None);
+        return;
+    };
+    let Ok(ctx) = egui_ctx.ctx_mut() else { return };
+    let screen_pos = *menu.screen_pos.get_or_insert_with(|| {
+        ctx.input(|input| input.pointer.latest_pos())
+            .unwrap_or_default()
+    });
+    let (energy, materials) = sellable.refund;
+    let mut is_closed = false;
+    egui::Window::new("sell_menu")
+        .title_bar(false)
+        .resizable(false)
+        .collapsible(false)
+        .fixed_pos(screen_pos)
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "Sell {} for {energy:.0} energy and {materials:.0} materials?",
+                sellable.name
+            ));
+            ui.horizontal(|ui| {
+                if ui.button("Sell").clicked() {
+                    sell_ev.write(SellMessage { tile_pos });
+                    is_closed = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    is_closed = true;
+                }
+            });
+        });
+    if is_closed {
+        menu.open(None);
+    }
+}
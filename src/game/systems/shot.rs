@@ -1,2 +1,4 @@
+pub mod ballistic;
 pub mod damage_in_radius_enemy_locked;
 pub mod damage_per_time;
+pub mod slow_in_radius;
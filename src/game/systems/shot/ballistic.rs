@@ -0,0 +1,45 @@
+use crate::{
+    game::actions::explosions::ExplosionMessage,
+    utils::{damage::Damage, interpolation::TickTranslation, shots::BallisticShot, IngameTime},
+};
+use bevy::prelude::*;
+
+// Scale of the grenade at the top of its arc, it looks closer to the camera
+const MAX_HEIGHT_SCALE: f32 = 1.6;
+
+pub fn fly_system(
+    mut q_shots: Query<(&mut BallisticShot, &mut TickTranslation)>,
+    time: Res<IngameTime>,
+) {
+    let now = time.now();
+    for (mut shot, mut translation) in q_shots.iter_mut() {
+        shot.fly(now);
+        translation.current = shot.scaled_pos(now);
+    }
+}
+
+pub fn explode_system(
+    mut cmds: Commands,
+    mut expl_ev: MessageWriter<ExplosionMessage>,
+    q_shots: Query<(Entity, &BallisticShot)>,
+    time: Res<IngameTime>,
+) {
+    for (entity, shot) in q_shots.iter() {
+        if shot.is_landed(time.now()) {
+            expl_ev.write(ExplosionMessage::new(
+                shot.target_pos,
+                shot.damage_radius,
+                Damage::new(shot.damage, shot.damage_type).with_armor_piercing(shot.armor_piercing),
+            ));
+            cmds.entity(entity).try_despawn();
+        }
+    }
+}
+
+pub fn visual_system(mut q_shots: Query<(&mut Transform, &BallisticShot)>, time: Res<IngameTime>) {
+    for (mut transform, shot) in q_shots.iter_mut() {
+        let height_share = shot.height(time.now()) / shot.arc_height.max(f32::EPSILON);
+        let scale = 1. + (MAX_HEIGHT_SCALE - 1.) * height_share;
+        transform.scale = Vec3::new(scale, scale, 1.);
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{
     board::BoardCache,
     game::{actions::explosions::ExplosionMessage, enemies::Enemy},
-    utils::{pos_to_quat, shots::DamageInRadiusTargetPosShot, IngameTime, Vec2Board},
+    utils::{
+        damage::Damage, interpolation::TickTranslation, pos_to_quat,
+        shots::DamageInRadiusTargetPosShot, IngameTime, Vec2Board,
+    },
 };
 use bevy::prelude::*;
 
@@ -17,7 +20,7 @@ pub fn damage_and_despawn_system(
             expl_ev.write(ExplosionMessage::new(
                 shot.pos,
                 shot.damage_radius,
-                shot.damage,
+                Damage::new(shot.damage, shot.damage_type).with_armor_piercing(shot.armor_piercing),
             ));
             cmds.entity(entity).try_despawn();
         }
@@ -29,30 +32,33 @@ fn is_explode(shot: &DamageInRadiusTargetPosShot) -> bool {
 }
 
 pub fn fly_system(
-    mut q_shots: Query<&mut DamageInRadiusTargetPosShot>,
+    mut q_shots: Query<(&mut DamageInRadiusTargetPosShot, &mut TickTranslation)>,
     q_enemies: QueryEnemies,
     time: Res<IngameTime>,
     board_cache: Res<BoardCache>,
 ) {
     let frame_dur = time.delta();
-    for mut shot in &mut q_shots {
+    for (mut shot, mut translation) in &mut q_shots {
         if let Some(target_id) = shot.target_id {
             if let Ok((_, enemy)) = q_enemies.get(target_id) {
                 shot.fly_to(enemy.pos, frame_dur);
             } else if let Some(enemy) = find_nearest_enemy(&q_enemies, shot.pos) {
                 shot.target_id = Some(enemy);
             } else {
-                shot.set_target_point_to_likely(&board_cache.road_path);
+                shot.set_target_point_to_likely(board_cache.all_road_paths());
             }
         } else {
             shot.fly(frame_dur);
         }
+        translation.current = shot.scaled_pos(translation.current.z);
     }
 }
 
+// Rockets don't hit air, so they don't switch to flying enemies
 fn find_nearest_enemy(q_enemies: &QueryEnemies, pos: Vec2Board) -> Option<Entity> {
     q_enemies
         .iter()
+        .filter(|(_, enemy)| !enemy.is_flying())
         .reduce(|item_1, item_2| {
             match item_1.1.pos.distance(pos.into()) < item_2.1.pos.distance(pos.into()) {
                 true => item_1,
@@ -64,7 +70,6 @@ fn find_nearest_enemy(q_enemies: &QueryEnemies, pos: Vec2Board) -> Option<Entity
 
 pub fn visual_system(mut q_shot: Query<(&mut Transform, &DamageInRadiusTargetPosShot)>) {
     for (mut transform, shot) in q_shot.iter_mut() {
-        transform.translation = shot.scaled_pos(transform.translation.z);
         transform.rotation = pos_to_quat(shot.pos, shot.target_pos);
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     game::{actions::damage::DamageMessage, enemies::Enemy},
-    utils::{pos_to_quat, shots::DamagePerTimeShot, IngameTime},
+    utils::{damage::Damage, pos_to_quat, shots::DamagePerTimeShot, IngameTime},
 };
 use bevy::prelude::*;
 
@@ -15,7 +15,9 @@ pub fn damage_system(
     let frame_dur = time.delta_secs();
     for shot in q_shots.iter() {
         if let Some((enemy_entity, _)) = find_enemy_in_range(&q_enemies, shot) {
-            dmg_ev.write(DamageMessage::new(enemy_entity, frame_dur * shot.damage));
+            let damage = Damage::new(frame_dur * shot.damage, shot.damage_type)
+                .with_armor_piercing(shot.armor_piercing);
+            dmg_ev.write(DamageMessage::new(enemy_entity, damage));
         }
     }
 }
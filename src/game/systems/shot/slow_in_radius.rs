@@ -0,0 +1,74 @@
+use crate::{
+    game::{
+        actions::{damage::DamageMessage, status_effects::StatusEffectMessage},
+        enemies::Enemy,
+    },
+    utils::{
+        damage::Damage, shots::SlowInRadiusShot, status_effects::StatusEffectKind, IngameTime,
+    },
+};
+use bevy::prelude::*;
+
+type EnemiesQuery<'w, 's, 'a> = Query<'w, 's, (Entity, &'a Enemy)>;
+
+pub fn pulse_system(
+    mut dmg_ev: MessageWriter<DamageMessage>,
+    mut effect_ev: MessageWriter<StatusEffectMessage>,
+    mut q_shots: Query<&mut SlowInRadiusShot>,
+    q_enemies: EnemiesQuery,
+    time: Res<IngameTime>,
+) {
+    let now = time.now();
+    for mut shot in q_shots.iter_mut() {
+        if now < shot.next_pulse {
+            continue;
+        }
+        let interval = shot.pulse_interval.as_secs_f32();
+        shot.next_pulse += interval;
+        for entity in enemies_in_range(&q_enemies, &shot) {
+            let damage = Damage::new(shot.damage, shot.damage_type);
+            dmg_ev.write(DamageMessage::new(entity, damage));
+            effect_ev.write(StatusEffectMessage::new(
+                entity,
+                StatusEffectKind::Slow(shot.slow_factor),
+                shot.slow_duration,
+            ));
+        }
+    }
+}
+
+pub fn despawn_system(
+    mut cmds: Commands,
+    q_shots: Query<(Entity, &SlowInRadiusShot)>,
+    q_enemies: EnemiesQuery,
+    time: Res<IngameTime>,
+) {
+    let now = time.now();
+    for (entity, shot) in q_shots.iter() {
+        if now >= shot.die_time || enemies_in_range(&q_enemies, shot).is_empty() {
+            cmds.entity(entity).try_despawn();
+        }
+    }
+}
+
+// Ring grows from the tower to the range edge between two pulses
+pub fn visual_system(
+    mut q_shots: Query<(&mut Transform, &SlowInRadiusShot)>,
+    time: Res<IngameTime>,
+) {
+    let now = time.now();
+    for (mut transform, shot) in q_shots.iter_mut() {
+        let interval = shot.pulse_interval.as_secs_f32();
+        let progress = 1. - (*shot.next_pulse - *now) / interval;
+        let scale = progress.clamp(0., 1.);
+        transform.scale = Vec3::new(scale, scale, 1.);
+    }
+}
+
+fn enemies_in_range(q_enemies: &EnemiesQuery, shot: &SlowInRadiusShot) -> Vec<Entity> {
+    q_enemies
+        .iter()
+        .filter(|(_, enemy)| enemy.is_in_range(shot.pos_start, shot.range_radius))
+        .map(|(entity, _)| entity)
+        .collect()
+}
@@ -0,0 +1,38 @@
+use crate::{
+    game::enemies::Enemy,
+    utils::{
+        speed::Speed,
+        status_effects::{StatusEffectType, StatusEffects},
+        visible, IngameTime,
+    },
+};
+use bevy::prelude::*;
+
+pub(super) fn status_effect_system(
+    mut q_enemies: Query<(&mut Enemy, &mut StatusEffects, &mut Speed)>,
+    time: Res<IngameTime>,
+) {
+    let now = time.now();
+    for (mut enemy, mut effects, mut speed) in q_enemies.iter_mut() {
+        effects.remove_expired(now);
+        // Runs before the acceleration, so slows win over the collision speed reset
+        speed.target = speed.normal * effects.speed_factor();
+        if effects.has(StatusEffectType::Stun) {
+            speed.current = 0.;
+        }
+        for damage in effects.burn_damages(time.delta_secs()) {
+            enemy.take_damage(&damage.scaled(effects.damage_multiplier()));
+        }
+    }
+}
+
+pub(super) fn status_effect_indicator_system(
+    mut q_indicators: Query<(&ChildOf, &StatusEffectType, &mut Visibility)>,
+    q_effects: Query<&StatusEffects>,
+) {
+    for (child_of, effect_type, mut visi) in q_indicators.iter_mut() {
+        if let Ok(effects) = q_effects.get(child_of.parent()) {
+            visi.set_if_neq(visible(effects.has(*effect_type)));
+        }
+    }
+}
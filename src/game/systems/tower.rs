@@ -1,10 +1,17 @@
 use crate::{
-    game::{actions::tower::TowerActionsMessage, build_menus::BuildMenuScreen, enemies::Enemy},
+    board::BoardCache,
+    game::{
+        actions::{resources::ResourcesMessage, tower::TowerActionsMessage},
+        build_menus::BuildMenuScreen,
+        enemies::Enemy,
+        Game,
+    },
     utils::{
         pos_to_quat,
         shots::{Shot, TowerStatus},
-        towers::{Tower, TowerCannon, TowerValues},
-        IngameTime, IngameTimestamp, Vec2Board,
+        speed::Speed,
+        towers::{Targeting, Tower, TowerCannon, TowerLevelPip, TowerValues},
+        visible, Energy, IngameTime, IngameTimestamp, Vec2Board,
     },
 };
 use bevy::prelude::*;
@@ -20,16 +27,52 @@ type QueryCannonDrawMut<'w, 's, 'a> =
 type EnemiesQuery<'w, 's, 'a> = Query<'w, 's, (Entity, &'a Enemy, &'a Children)>;
 type EntityEnemy<'a> = (Entity, &'a Enemy);
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn tower_target_system(
     mut tower_acts: MessageWriter<TowerActionsMessage>,
+    mut res_acts: MessageWriter<ResourcesMessage>,
     mut q_towers: Query<&mut Tower, Without<BuildMenuScreen>>,
     q_enemies: EnemiesQuery,
+    q_speeds: Query<&Speed>,
+    game: Res<Game>,
+    board_cache: Res<BoardCache>,
     time: Res<IngameTime>,
 ) {
+    // Drains and grenade costs reach the game only after this system, so they are counted here
+    let mut energy_left = game.energy();
+    let mut materials_left = game.materials();
     for mut tower in q_towers.iter_mut() {
         let vals = tower.values_mut();
-        let enemy = lock_tower_to_enemy(vals, &q_enemies);
-        shoot_or_reload(&mut tower_acts, vals, enemy, time.now());
+        let has_resources = match &vals.shot {
+            Shot::Microwave(shot) => energy_left >= shot.energy.size,
+            Shot::Grenade(shot) => materials_left >= shot.materials_per_shot,
+            _ => true,
+        };
+        let enemy = match vals.shot {
+            // Area shots hit everything in range
+            Shot::Microwave(_) => find_target(vals, &q_enemies, &q_speeds, &board_cache),
+            _ => lock_tower_to_enemy(vals, &q_enemies, &q_speeds, &board_cache),
+        }
+        .filter(|_| has_resources);
+        let landing_pos = match &vals.shot {
+            Shot::Grenade(shot) => enemy.map(|(entity, enemy)| {
+                let speed = q_speeds.get(entity).map_or(0., |speed| speed.current);
+                enemy.predicted_pos(shot.flight_duration, speed, &board_cache)
+            }),
+            _ => None,
+        };
+        let has_enemy = enemy.is_some();
+        let was_waiting = matches!(vals.tower_status, TowerStatus::Waiting);
+        shoot_or_reload(&mut tower_acts, vals, enemy, landing_pos, time.now());
+        if let (true, TowerStatus::Shooting(_), Shot::Grenade(shot)) =
+            (was_waiting, &vals.tower_status, &vals.shot)
+        {
+            materials_left -= shot.materials_per_shot;
+        }
+        let drain = drain_energy(vals, &mut energy_left, has_enemy, time.delta(), time.now());
+        if let Some(energy) = drain {
+            res_acts.write(ResourcesMessage::Energy(energy, vals.pos));
+        }
     }
 }
 
@@ -60,6 +103,20 @@ pub(super) fn tower_overheat_system(
     }
 }
 
+pub(super) fn tower_level_system(
+    mut q_pips: Query<(&TowerLevelPip, &mut Visibility)>,
+    q_towers: QueryTowersAndChildren,
+) {
+    for (tower, children) in q_towers.iter() {
+        let level = tower.values().level;
+        for child in children.iter() {
+            if let Ok((pip, mut visi)) = q_pips.get_mut(child) {
+                visi.set_if_neq(visible(level >= pip.0));
+            }
+        }
+    }
+}
+
 fn target_enemy<'a>(
     q_enemies: &'a EnemiesQuery,
     locked_enemy: Option<Entity>,
@@ -72,32 +129,63 @@ fn target_enemy<'a>(
     None
 }
 
+// A shooting tower stays on its enemy, otherwise the targeting picks again
 fn lock_tower_to_enemy<'a>(
     tower_vals: &mut TowerValues,
     enemies: &'a EnemiesQuery,
+    speeds: &Query<&Speed>,
+    board_cache: &BoardCache,
 ) -> Option<EntityEnemy<'a>> {
-    match tower_vals.target_lock {
-        Some(locked_entity) => {
-            match find_locked_enemy_in_tower_range(locked_entity, enemies, tower_vals) {
-                Some(locked_enemy) => return Some((locked_entity, locked_enemy)),
-                None => tower_vals.target_lock = None,
-            }
-        }
-        None => tower_vals.target_lock = find_first_enemy_entity_in_range(tower_vals, enemies),
-    }
-    None
+    let target = match tower_vals.tower_status {
+        TowerStatus::Shooting(_) => tower_vals.target_lock.and_then(|locked_entity| {
+            find_locked_enemy_in_tower_range(locked_entity, enemies, tower_vals)
+                .map(|locked_enemy| (locked_entity, locked_enemy))
+        }),
+        _ => find_target(tower_vals, enemies, speeds, board_cache),
+    };
+    tower_vals.target_lock = target.map(|(entity, _)| entity);
+    target
 }
 
-fn find_first_enemy_entity_in_range(
+fn find_target<'a>(
     tower_vals: &TowerValues,
-    enemies: &EnemiesQuery,
-) -> Option<Entity> {
-    for (entity, enemy, _) in enemies.iter() {
-        if enemy.is_in_range(tower_vals.pos, tower_vals.range_radius) {
-            return Some(entity);
-        }
+    enemies: &'a EnemiesQuery,
+    speeds: &Query<&Speed>,
+    board_cache: &BoardCache,
+) -> Option<EntityEnemy<'a>> {
+    enemies
+        .iter()
+        .filter(|(_, enemy, _)| can_target(tower_vals, enemy))
+        .map(|(entity, enemy, _)| {
+            let score = target_score(tower_vals, entity, enemy, speeds, board_cache);
+            (entity, enemy, score)
+        })
+        .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+        .map(|(entity, enemy, _)| (entity, enemy))
+}
+
+// Lowest score gets picked
+fn target_score(
+    tower_vals: &TowerValues,
+    entity: Entity,
+    enemy: &Enemy,
+    speeds: &Query<&Speed>,
+    board_cache: &BoardCache,
+) -> f32 {
+    match tower_vals.targeting {
+        Targeting::First => enemy.distance_to_end(board_cache),
+        Targeting::Last => -enemy.distance_to_end(board_cache),
+        Targeting::Strongest => -enemy.health,
+        Targeting::Weakest => enemy.health,
+        Targeting::Closest => enemy.pos.distance(tower_vals.pos.into()),
+        Targeting::Fastest => -speeds.get(entity).map_or(0., |speed| speed.current),
     }
-    None
+}
+
+fn can_target(tower_vals: &TowerValues, enemy: &Enemy) -> bool {
+    (tower_vals.can_hit_air || !enemy.is_flying())
+        && enemy.is_in_range(tower_vals.pos, tower_vals.range_radius)
+        && enemy.pos.distance(tower_vals.pos.into()) >= tower_vals.shot.min_range_radius()
 }
 
 fn find_locked_enemy_in_tower_range<'a>(
@@ -106,7 +194,7 @@ fn find_locked_enemy_in_tower_range<'a>(
     tower_vals: &TowerValues,
 ) -> Option<&'a Enemy> {
     if let Ok((_, enemy, _)) = q_enemies.get(locked_enemy_entity) {
-        if enemy.is_in_range(tower_vals.pos, tower_vals.range_radius) {
+        if can_target(tower_vals, enemy) {
             return Some(enemy);
         }
     }
@@ -185,6 +273,7 @@ fn shoot_or_reload(
     actions: &mut MessageWriter<TowerActionsMessage>,
     vals: &mut TowerValues,
     enemy: Option<EntityEnemy>,
+    landing_pos: Option<Vec2Board>,
     now: IngameTimestamp,
 ) {
     match vals.tower_status {
@@ -194,7 +283,7 @@ fn shoot_or_reload(
             }
         }
         TowerStatus::Waiting => {
-            if shoot(actions, &vals.shot, enemy) {
+            if shoot(actions, &vals.shot, enemy, landing_pos) {
                 vals.tower_status = TowerStatus::Shooting(now + vals.shoot_duration);
             }
         }
@@ -207,6 +296,29 @@ fn shoot_or_reload(
     };
 }
 
+// Continuous drain while shooting, paid in packages like the factory does.
+// Without a full package or an enemy in range the tower stops shooting.
+fn drain_energy(
+    vals: &mut TowerValues,
+    energy_left: &mut Energy,
+    has_enemy: bool,
+    frame_dur: Duration,
+    now: IngameTimestamp,
+) -> Option<Energy> {
+    let (TowerStatus::Shooting(_), Shot::Microwave(shot)) = (&vals.tower_status, &mut vals.shot)
+    else {
+        return None;
+    };
+    if !has_enemy || *energy_left < shot.energy.size {
+        let reload_finish = now + vals.reload_duration;
+        set_tower_status_reload(vals, reload_finish);
+        return None;
+    }
+    let energy = shot.energy.consume_during(frame_dur)?;
+    *energy_left += energy;
+    Some(energy)
+}
+
 fn set_tower_status_reload(tower_vals: &mut TowerValues, finish: IngameTimestamp) {
     tower_vals.tower_status = TowerStatus::Reloading(finish);
 }
@@ -215,6 +327,7 @@ fn shoot(
     actions: &mut MessageWriter<TowerActionsMessage>,
     shot: &Shot,
     enemy: Option<EntityEnemy>,
+    landing_pos: Option<Vec2Board>,
 ) -> bool {
     match shot {
         Shot::Laser(shot) => {
@@ -233,6 +346,81 @@ fn shoot(
                 return true;
             }
         }
+        Shot::Microwave(shot) => {
+            if enemy.is_some() {
+                actions.write(TowerActionsMessage::ShootMicrowave(shot.clone()));
+                return true;
+            }
+        }
+        Shot::Grenade(shot) => {
+            if let Some(target_pos) = landing_pos {
+                actions.write(TowerActionsMessage::ShootGrenade(shot.clone(), target_pos));
+                return true;
+            }
+        }
     };
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        balance::GRENADE_MATERIALS_PER_SHOT, board::map_file::get_all_boards_in_folder,
+        utils::Difficulty,
+    };
+
+    #[test]
+    fn test_microwave_drain_keeps_energy_positive() {
+        let mut vals = TowerValues::microwave(Vec2Board::new(1., 1.));
+        vals.tower_status = TowerStatus::Shooting(IngameTimestamp::new(1000.));
+        let mut energy_left = 12.;
+        let tick = Duration::from_secs_f32(1. / 60.);
+        for i in 0..60 * 60 {
+            let now = IngameTimestamp::new(i as f32 / 60.);
+            drain_energy(&mut vals, &mut energy_left, true, tick, now);
+            assert!(energy_left >= 0.);
+        }
+        assert!(matches!(vals.tower_status, TowerStatus::Reloading(_)));
+    }
+
+    #[test]
+    fn test_grenades_of_one_tick_share_materials() {
+        let board = get_all_boards_in_folder()
+            .unwrap()
+            .boards
+            .into_iter()
+            .find(|board| board.name == "0test")
+            .unwrap();
+        let mut game = Game::new(Difficulty::Easy, 1);
+        // Enough for one grenade only
+        game.materials = GRENADE_MATERIALS_PER_SHOT * 1.5;
+
+        let mut app = App::new();
+        app.add_message::<TowerActionsMessage>()
+            .add_message::<ResourcesMessage>()
+            .insert_resource(game)
+            .insert_resource(BoardCache::new(&board))
+            .insert_resource(IngameTime::from_elapsed(Duration::ZERO))
+            .add_systems(Update, tower_target_system);
+        let world = app.world_mut();
+        let enemy_pos = Vec2Board::new(3., 3.);
+        world
+            .spawn(Enemy::new_dummy(enemy_pos))
+            .with_children(|parent| {
+                parent.spawn_empty();
+            });
+        for x in [1.5, 4.5] {
+            world.spawn(Tower::grenade(Vec2Board::new(x, 3.)));
+        }
+        app.update();
+
+        let world = app.world_mut();
+        let shots = world
+            .query::<&Tower>()
+            .iter(world)
+            .filter(|tower| matches!(tower.values().tower_status, TowerStatus::Shooting(_)))
+            .count();
+        assert_eq!(shots, 1);
+    }
+}
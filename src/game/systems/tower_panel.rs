@@ -0,0 +1,111 @@
+use crate::{
+    balance::TOWER_MAX_LEVEL,
+    game::{
+        actions::{
+            build_menu::tower_upgrade_cost,
+            tower::{TowerCommand, TowerCommandMessage},
+        },
+        Game, GameScreen,
+    },
+    utils::{
+        towers::{Targeting, Tower, Towerless},
+        BoardPos,
+    },
+};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2, Button, Grid},
+    EguiContexts,
+};
+use strum::IntoEnumIterator;
+
+// Tower clicked on the board, its panel stays open until closed or the tower is gone
+#[derive(Resource, Default)]
+pub(in crate::game) struct TowerPanel {
+    pub tile_pos: Option<UVec2>,
+}
+
+pub(super) fn tower_panel(
+    mut egui_ctx: EguiContexts,
+    mut panel: ResMut<TowerPanel>,
+    mut commands: MessageWriter<TowerCommandMessage>,
+    q_towers: Query<(&BoardPos, &Tower), With<GameScreen>>,
+    game: Res<Game>,
+) {
+    let Some(tile_pos) = panel.tile_pos else {
+        return;
+    };
+    let Some((_, tower)) = q_towers.iter().find(|(pos, _)| ***pos == tile_pos) else {
+        panel.tile_pos = None;
+        return;
+    };
+    let Ok(ctx) = egui_ctx.ctx_mut() else { return };
+    let kind = Towerless::from(tower);
+    let mut is_open = true;
+    egui::Window::new(format!("{kind:?} tower"))
+        .anchor(Align2::RIGHT_TOP, [-16., 16.])
+        .collapsible(false)
+        .resizable(false)
+        .open(&mut is_open)
+        .show(ctx, |ui| {
+            let vals = tower.values();
+            Grid::new("tower_panel_stats").show(ui, |ui| {
+                let stats = [
+                    ("Level", format!("{}/{TOWER_MAX_LEVEL}", vals.level)),
+                    ("Range", format!("{:.2}", vals.range_radius)),
+                    ("Damage", format!("{:.0}", vals.shot.damage())),
+                    (
+                        "Reload",
+                        format!("{:.2}s", vals.reload_duration.as_secs_f32()),
+                    ),
+                    ("Shot", format!("{:.2}s", vals.shoot_duration.as_secs_f32())),
+                ];
+                for (label, value) in stats {
+                    ui.label(label);
+                    ui.label(value);
+                    ui.end_row();
+                }
+            });
+            ui.separator();
+
+            match tower_upgrade_cost(tower) {
+                Some((energy, materials)) => {
+                    let can_afford = game.energy() >= energy && game.materials() >= materials;
+                    let text = format!("Upgrade: {energy:.0} energy, {materials:.0} materials");
+                    if ui.add_enabled(can_afford, Button::new(text)).clicked() {
+                        commands.write(TowerCommandMessage {
+                            tile_pos,
+                            command: TowerCommand::Upgrade,
+                        });
+                    }
+                }
+                None => {
+                    ui.label("Max level");
+                }
+            }
+            ui.separator();
+
+            // Area shots hit every enemy in range anyway
+            if kind == Towerless::Microwave {
+                ui.label("Hits all enemies in range");
+                return;
+            }
+            ui.label("Targeting");
+            for targeting in Targeting::iter() {
+                let is_selected = vals.targeting == targeting;
+                if ui
+                    .selectable_label(is_selected, targeting.to_string())
+                    .clicked()
+                    && !is_selected
+                {
+                    commands.write(TowerCommandMessage {
+                        tile_pos,
+                        command: TowerCommand::SetTargeting(targeting),
+                    });
+                }
+            }
+        });
+    if !is_open {
+        panel.tile_pos = None;
+    }
+}
@@ -1,14 +1,15 @@
 use crate::balance::{
-    TANK_SPAWN_EVERY_NTH_ENEMY, WAVE_BASE_ENEMY_COUNT, WAVE_ENEMIES_PER_WAVE,
-    WAVE_ENEMY_SPAWN_INTERVAL_DECREASE_PER_WAVE_SECS, WAVE_MIN_ENEMY_SPAWN_INTERVAL_SECS,
+    WAVE_BASE_ENEMY_COUNT, WAVE_ENEMIES_PER_WAVE, WAVE_ENEMY_SPAWN_INTERVAL_DECREASE_PER_WAVE_SECS,
+    WAVE_MIN_ENEMY_SPAWN_INTERVAL_SECS, WAVE_SINGLE_ENTRANCE_EVERY_NTH_WAVE,
     WAVE_START_ENEMY_SPAWN_INTERVAL_SECS,
 };
 use crate::board::BoardCache;
 use crate::game::actions::wave::WaveActionsMessage;
-use crate::game::enemies::{Enemy, EnemyType};
+use crate::game::enemies::{definitions::EnemyDefinitions, Enemy};
 use crate::game::Game;
-use crate::utils::{IngameTime, IngameTimestamp};
+use crate::utils::{GameRng, IngameTime, IngameTimestamp};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 #[derive(States, Clone, Copy, Eq, PartialEq, Debug, Hash, PartialOrd, Ord, Default)]
@@ -18,16 +19,27 @@ pub enum WaveState {
     Running,
 }
 
-#[derive(Resource, Clone)]
+// Road entrances the enemies of a wave come through
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub enum WaveEntrances {
+    // Take turns on all entrances
+    #[default]
+    Spread,
+    Only(usize),
+}
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
 pub struct Wave {
     pub next_enemy_spawn: IngameTimestamp,
     enemies_spawned: u32,
     max_enemies: u32,
     enemy_spawn_interval: Duration,
+    entrances: WaveEntrances,
+    wave_no: u32,
 }
 
 impl Wave {
-    pub fn new(wave_no: u32, next_enemy_spawn: IngameTimestamp) -> Self {
+    pub fn new(wave_no: u32, next_enemy_spawn: IngameTimestamp, entrance_count: usize) -> Self {
         let spawn_interval_secs = (WAVE_START_ENEMY_SPAWN_INTERVAL_SECS
             - (wave_no.saturating_sub(1) as f32
                 * WAVE_ENEMY_SPAWN_INTERVAL_DECREASE_PER_WAVE_SECS))
@@ -37,6 +49,23 @@ impl Wave {
             next_enemy_spawn,
             max_enemies: WAVE_BASE_ENEMY_COUNT + (wave_no * WAVE_ENEMIES_PER_WAVE),
             enemy_spawn_interval: Duration::from_secs_f32(spawn_interval_secs),
+            entrances: Self::entrances(wave_no, entrance_count),
+            wave_no,
+        }
+    }
+
+    fn entrances(wave_no: u32, entrance_count: usize) -> WaveEntrances {
+        let nth = WAVE_SINGLE_ENTRANCE_EVERY_NTH_WAVE;
+        match entrance_count > 1 && wave_no.is_multiple_of(nth) {
+            true => WaveEntrances::Only((wave_no / nth) as usize % entrance_count),
+            false => WaveEntrances::Spread,
+        }
+    }
+
+    pub fn next_entrance(&self, entrance_count: usize) -> usize {
+        match self.entrances {
+            WaveEntrances::Spread => self.enemies_spawned as usize % entrance_count,
+            WaveEntrances::Only(entrance) => entrance % entrance_count,
         }
     }
 
@@ -72,9 +101,12 @@ pub(in crate::game) fn wave_system(
     q_enemies: Query<&Enemy>,
     time: Res<IngameTime>,
     board_cache: Res<BoardCache>,
-    wave_state: Res<State<WaveState>>,
+    game: Res<Game>,
+    mut rng: ResMut<GameRng>,
+    definitions: Res<EnemyDefinitions>,
 ) {
-    if *wave_state == WaveState::Running {
+    // The wave state changes only once per frame, the game knows it on every tick
+    if !game.is_between_waves() {
         let is_wave_end = wave.is_wave_end();
         let now = IngameTimestamp::new(time.elapsed_secs());
 
@@ -85,7 +117,14 @@ pub(in crate::game) fn wave_system(
 
         // Spawn enemy on next spawn time point
         if !is_wave_end && now >= wave.next_enemy_spawn {
-            spawn_enemy_and_prepare_next(&mut cmds, &mut wave, &q_enemies, &board_cache);
+            spawn_enemy_and_prepare_next(
+                &mut cmds,
+                &mut wave,
+                &q_enemies,
+                &board_cache,
+                &mut rng,
+                &definitions,
+            );
         }
     }
 }
@@ -95,13 +134,49 @@ fn spawn_enemy_and_prepare_next(
     wave: &mut Wave,
     q_enemies: &Query<&Enemy>,
     board_cache: &BoardCache,
+    rng: &mut GameRng,
+    definitions: &EnemyDefinitions,
 ) {
-    let enemy_type = match (wave.enemies_spawned + 1).rem_euclid(TANK_SPAWN_EVERY_NTH_ENEMY) {
-        0 => EnemyType::Tank,
-        _ => EnemyType::Normal,
-    };
-    if let Some(enemy) = Enemy::new(enemy_type, q_enemies, board_cache) {
-        enemy.spawn(cmds);
+    let definition = definitions.select(wave.wave_no, wave.enemies_spawned + 1);
+    let entrance = wave.next_entrance(board_cache.road_entrances.len());
+    // An entrance without route would block the wave forever, its enemy is skipped
+    if board_cache.road_entrances[entrance].road_paths.is_empty() {
+        wave.prepare_next_enemy_spawn();
+        return;
+    }
+    if let Some(enemy) = Enemy::new(definition, entrance, q_enemies, board_cache, rng) {
+        enemy.spawn(cmds, definitions);
+        wave.prepare_next_enemy_spawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wave_spreads_over_entrances() {
+        let mut wave = Wave::new(1, IngameTimestamp::new(0.), 3);
+        let entrances: Vec<usize> = (0..4)
+            .map(|_| {
+                let entrance = wave.next_entrance(3);
+                wave.prepare_next_enemy_spawn();
+                entrance
+            })
+            .collect();
+        assert_eq!(entrances, vec![0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn test_wave_targets_single_entrance() {
+        let nth = WAVE_SINGLE_ENTRANCE_EVERY_NTH_WAVE;
+        let mut wave = Wave::new(nth, IngameTimestamp::new(0.), 2);
+        assert_eq!(wave.entrances, WaveEntrances::Only(1));
         wave.prepare_next_enemy_spawn();
+        assert_eq!(wave.next_entrance(2), 1);
+        assert_eq!(
+            Wave::new(nth, IngameTimestamp::new(0.), 1).entrances,
+            WaveEntrances::Spread
+        );
     }
 }
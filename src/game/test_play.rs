@@ -0,0 +1,40 @@
+use super::Game;
+use crate::{
+    board::{Board, BoardCache},
+    utils::{random_seed, Difficulty, GameState},
+};
+use bevy::prelude::*;
+
+// Resources are held at this level with infinite resources
+const TEST_PLAY_RESOURCES: f32 = 1_000_000.;
+
+// Match started from the board editor, which gets back its board afterwards
+#[derive(Resource, Clone)]
+pub(crate) struct TestPlay {
+    pub board: Board,
+    pub difficulty: Difficulty,
+    pub infinite_resources: bool,
+}
+
+impl TestPlay {
+    // Where a match ends up when it's left
+    pub fn exit_state(test_play: Option<&TestPlay>) -> GameState {
+        match test_play {
+            Some(_) => GameState::MapEditor,
+            None => GameState::Menu,
+        }
+    }
+}
+
+pub(super) fn start_test_play(mut cmds: Commands, test_play: Res<TestPlay>) {
+    cmds.insert_resource(Game::new(test_play.difficulty, random_seed()));
+    cmds.insert_resource(BoardCache::new(&test_play.board));
+    cmds.insert_resource(test_play.board.clone());
+}
+
+pub(super) fn fill_test_play_resources(mut game: ResMut<Game>, test_play: Res<TestPlay>) {
+    if test_play.infinite_resources {
+        game.energy = game.energy.max(TEST_PLAY_RESOURCES);
+        game.materials = game.materials.max(TEST_PLAY_RESOURCES);
+    }
+}
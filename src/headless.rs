@@ -0,0 +1,336 @@
+use crate::{
+    board::{map_file::get_all_boards_in_folder, BoardCache},
+    game::{
+        replay::{Replay, ReplayPlayback},
+        statistics::{EnemyKillCount, GrenadesFired, LaserShotsFired, RocketsFired, TowerUpgrades},
+        Game, GameSimulation, IngameState, SIMULATION_TICK,
+    },
+    utils::{random_seed, Difficulty, GameState, IngameTime, Seed},
+};
+use bevy::{
+    app::ScheduleRunnerPlugin, prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy,
+};
+use serde::Serialize;
+use std::{error::Error, fs::File, io::Write, path::Path, str::FromStr, time::Duration};
+
+pub const HEADLESS_ARG: &str = "--headless";
+
+// Runs a full match without window, rendering or egui and reports the outcome
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct HeadlessConfig {
+    pub map_name: String,
+    pub difficulty: Difficulty,
+    pub seed: Seed,
+    pub speed: f32,
+    pub max_waves: Option<u32>,
+    pub out_file: Option<String>,
+    // Map, difficulty and seed come from the replay then
+    pub replay_file: Option<String>,
+}
+
+impl HeadlessConfig {
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut map_name = None;
+        let mut difficulty = Difficulty::Easy;
+        let mut seed = random_seed();
+        let mut speed = 1.;
+        let mut max_waves = None;
+        let mut out_file = None;
+        let mut replay_file = None;
+
+        let mut args = args.iter().filter(|arg| *arg != HEADLESS_ARG);
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .cloned()
+                    .ok_or_else(|| format!("Missing value for '{arg}'"))
+            };
+            match arg.as_str() {
+                "--map" => map_name = Some(value()?),
+                "--difficulty" => difficulty = parse_value(arg, &value()?)?,
+                "--seed" => seed = parse_value(arg, &value()?)?,
+                "--speed" => speed = parse_value(arg, &value()?)?,
+                "--max-waves" => max_waves = Some(parse_value(arg, &value()?)?),
+                "--out" => out_file = Some(value()?),
+                "--replay" => replay_file = Some(value()?),
+                _ => return Err(format!("Unknown argument '{arg}'")),
+            }
+        }
+
+        Ok(Self {
+            map_name: match (map_name, &replay_file) {
+                (Some(map_name), _) => map_name,
+                (None, Some(_)) => String::new(),
+                (None, None) => return Err("Missing '--map <name>' or '--replay <file>'".into()),
+            },
+            difficulty,
+            seed,
+            speed,
+            max_waves,
+            out_file,
+            replay_file,
+        })
+    }
+}
+
+fn parse_value<T: FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value '{value}' for '{arg}'"))
+}
+
+// Machine-readable result of a headless match
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchOutcome {
+    pub map_name: String,
+    pub difficulty: Difficulty,
+    pub seed: Seed,
+    pub is_game_over: bool,
+    pub wave_reached: u32,
+    pub kills: u64,
+    pub energy: f32,
+    pub materials: f32,
+    pub laser_shots_fired: u64,
+    pub rockets_fired: u64,
+    pub grenades_fired: u64,
+    pub tower_upgrades: u64,
+    pub ingame_secs: f32,
+}
+
+pub fn run(args: &[String]) -> AppExit {
+    let config = match HeadlessConfig::from_args(args) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}");
+            return AppExit::error();
+        }
+    };
+    match headless_app(config) {
+        Ok(mut app) => app.run(),
+        Err(err) => {
+            eprintln!("{err}");
+            AppExit::error()
+        }
+    }
+}
+
+pub fn headless_app(mut config: HeadlessConfig) -> Result<App, Box<dyn Error>> {
+    let (board, playback) = match &config.replay_file {
+        Some(path) => {
+            let replay = Replay::from_file(Path::new(path))?;
+            config.map_name = replay.board.name.clone();
+            config.difficulty = replay.difficulty;
+            config.seed = replay.seed;
+            (
+                replay.board.clone(),
+                Some(ReplayPlayback::new(&replay).without_game_actions()),
+            )
+        }
+        None => {
+            let board = get_all_boards_in_folder()?
+                .boards
+                .into_iter()
+                .find(|board| board.name == config.map_name)
+                .ok_or(format!("Map '{}' not found", config.map_name))?;
+            (board, None)
+        }
+    };
+    let board_cache = BoardCache::new(&board);
+    board_cache.validate()?;
+
+    let game = Game::new(config.difficulty, config.seed).with_speed(config.speed);
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::ZERO)),
+        StatesPlugin,
+        GameSimulation,
+    ))
+    .insert_resource(TimeUpdateStrategy::ManualDuration(SIMULATION_TICK))
+    .insert_resource(game)
+    .insert_resource(board)
+    .insert_resource(board_cache)
+    .insert_resource(config)
+    .insert_state(GameState::Game)
+    .add_systems(
+        Update,
+        finish_on_wave_limit.run_if(in_state(IngameState::Running)),
+    )
+    .add_systems(OnEnter(IngameState::GameOver), finish_on_game_over);
+    if let Some(playback) = playback {
+        app.insert_resource(playback);
+    }
+    Ok(app)
+}
+
+fn finish_on_wave_limit(
+    exit: MessageWriter<AppExit>,
+    config: Res<HeadlessConfig>,
+    stats: OutcomeStats,
+) {
+    if let Some(max_waves) = config.max_waves {
+        if stats.game.wave_no() >= max_waves && stats.game.is_between_waves() {
+            finish(exit, &config, &stats, false);
+        }
+    }
+}
+
+fn finish_on_game_over(
+    exit: MessageWriter<AppExit>,
+    config: Res<HeadlessConfig>,
+    stats: OutcomeStats,
+) {
+    finish(exit, &config, &stats, true);
+}
+
+#[derive(bevy::ecs::system::SystemParam)]
+struct OutcomeStats<'w> {
+    game: Res<'w, Game>,
+    time: Res<'w, IngameTime>,
+    kill_count: Res<'w, EnemyKillCount>,
+    laser_count: Res<'w, LaserShotsFired>,
+    rocket_count: Res<'w, RocketsFired>,
+    grenade_count: Res<'w, GrenadesFired>,
+    upgrade_count: Res<'w, TowerUpgrades>,
+}
+
+fn finish(
+    mut exit: MessageWriter<AppExit>,
+    config: &HeadlessConfig,
+    stats: &OutcomeStats,
+    is_game_over: bool,
+) {
+    let outcome = MatchOutcome {
+        map_name: config.map_name.clone(),
+        difficulty: config.difficulty,
+        seed: stats.game.seed(),
+        is_game_over,
+        wave_reached: stats.game.wave_no(),
+        kills: stats.kill_count.0,
+        energy: stats.game.energy(),
+        materials: stats.game.materials(),
+        laser_shots_fired: stats.laser_count.0,
+        rockets_fired: stats.rocket_count.0,
+        grenades_fired: stats.grenade_count.0,
+        tower_upgrades: stats.upgrade_count.0,
+        ingame_secs: stats.time.elapsed_secs(),
+    };
+    match write_outcome(&outcome, config.out_file.as_deref()) {
+        Ok(()) => exit.write(AppExit::Success),
+        Err(err) => {
+            eprintln!("{err}");
+            exit.write(AppExit::error())
+        }
+    };
+}
+
+fn write_outcome(outcome: &MatchOutcome, out_file: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let json = serde_json::to_string(outcome)?;
+    match out_file {
+        Some(path) => write!(File::create(path)?, "{json}")?,
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::interpolation::TickTranslation;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_config_from_args() {
+        let config = HeadlessConfig::from_args(&args(&[
+            "--headless",
+            "--map",
+            "Fat Cat",
+            "--difficulty",
+            "Hard",
+            "--max-waves",
+            "3",
+        ]))
+        .unwrap();
+        assert_eq!(config.map_name, "Fat Cat");
+        assert_eq!(config.difficulty, Difficulty::Hard);
+        assert_eq!(config.max_waves, Some(3));
+        assert_eq!(config.speed, 1.);
+    }
+
+    #[test]
+    fn test_config_needs_map() {
+        assert!(HeadlessConfig::from_args(&args(&["--headless"])).is_err());
+    }
+
+    #[test]
+    fn test_config_rejects_unknown_args() {
+        assert!(HeadlessConfig::from_args(&args(&["--map", "Haken", "--fast"])).is_err());
+    }
+
+    #[test]
+    fn test_headless_match_reaches_wave_limit() {
+        let config = HeadlessConfig::from_args(&args(&["--map", "0test", "--max-waves", "1"]));
+        let mut app = headless_app(config.unwrap()).unwrap();
+        for _ in 0..20_000 {
+            app.update();
+            if app.should_exit().is_some() {
+                break;
+            }
+        }
+        assert_eq!(app.should_exit(), Some(AppExit::Success));
+        assert_eq!(app.world().resource::<Game>().wave_no(), 1);
+    }
+
+    fn app_with(seed: &str, speed: &str) -> App {
+        let config =
+            HeadlessConfig::from_args(&args(&["--map", "0test", "--seed", seed, "--speed", speed]));
+        headless_app(config.unwrap()).unwrap()
+    }
+
+    fn translations(app: &mut App) -> Vec<Vec3> {
+        let world = app.world_mut();
+        let mut q_translations = world.query::<&TickTranslation>();
+        q_translations.iter(world).map(|t| t.current).collect()
+    }
+
+    fn elapsed(app: &App) -> Duration {
+        app.world().resource::<IngameTime>().elapsed()
+    }
+
+    #[test]
+    fn test_same_seed_plays_same_match() {
+        let mut app_1 = app_with("7", "1");
+        let mut app_2 = app_with("7", "1");
+        for _ in 0..1_500 {
+            app_1.update();
+            app_2.update();
+        }
+        assert!(!translations(&mut app_1).is_empty());
+        assert_eq!(translations(&mut app_1), translations(&mut app_2));
+    }
+
+    #[test]
+    fn test_game_speed_does_not_change_match() {
+        let mut fast_app = app_with("3", "30");
+        for _ in 0..100 {
+            fast_app.update();
+        }
+        let mut slow_app = app_with("3", "1");
+        slow_app.update();
+        while elapsed(&slow_app) < elapsed(&fast_app) {
+            slow_app.update();
+        }
+        assert_eq!(elapsed(&slow_app), elapsed(&fast_app));
+        assert!(!translations(&mut fast_app).is_empty());
+        assert_eq!(translations(&mut slow_app), translations(&mut fast_app));
+        let (slow_game, fast_game) = (
+            slow_app.world().resource::<Game>(),
+            fast_app.world().resource::<Game>(),
+        );
+        assert_eq!(slow_game.energy(), fast_game.energy());
+        assert_eq!(slow_game.wave_no(), fast_game.wave_no());
+    }
+}
@@ -23,6 +23,7 @@ mod board;
 mod board_editor;
 mod controls;
 mod game;
+mod headless;
 mod main_menu;
 mod menu_panels;
 mod splash;
@@ -39,7 +40,40 @@ const BACKGROUND_COLOR: Color = Color::srgba(35.0 / 255.0, 33.0 / 255.0, 38.0 /
 // const TEXT_COLOR: Color = Color::rgb(232.0 / 255.0, 230.0 / 255.0, 227.0 / 255.0);
 // const FONT_PATH: &str = "fonts/Quicksand-Regular.ttf";
 
-fn main() {
+// Upgrades all maps of the maps folder to the current file format
+fn resave_maps() -> AppExit {
+    match board::map_file::resave_all_maps() {
+        Ok(folder) => {
+            for board in folder.boards.iter() {
+                println!("Resaved '{}'", board.name);
+            }
+            for err in folder.errors.iter() {
+                eprintln!("{err}");
+            }
+            match folder.errors.is_empty() {
+                true => AppExit::Success,
+                false => AppExit::error(),
+            }
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            AppExit::error()
+        }
+    }
+}
+
+fn main() -> AppExit {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == headless::HEADLESS_ARG) {
+        return headless::run(&args);
+    }
+    if args
+        .iter()
+        .any(|arg| arg == board::map_file::RESAVE_MAPS_ARG)
+    {
+        return resave_maps();
+    }
+
     let mut app = App::new();
 
     app.insert_resource(ClearColor(BACKGROUND_COLOR))
@@ -79,7 +113,7 @@ fn main() {
             setup_cameras.before(EguiStartupSet::InitContexts),
         )
         .add_systems(Startup, setup_egui)
-        .run();
+        .run()
 }
 
 fn setup_cameras(mut commands: Commands) {
@@ -1,16 +1,23 @@
-use super::{new_game_menu::NewGameMenu, MenuState};
+use super::{new_game_menu::NewGameMenu, LatestSave, MenuState};
 use crate::{
     board::{Board, BoardCache},
-    game::Game,
+    game::{
+        replay::{Replay, ReplayPlayback},
+        save::SaveGame,
+        Game,
+    },
     utils::GameState,
 };
 use bevy::prelude::*;
+use std::path::{Path, PathBuf};
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Message)]
 pub(super) enum MenuActionMessage {
     EnterNewGameMenu,
     StartNewGame(Game, Board, BoardCache),
+    Continue(PathBuf),
+    WatchReplay(PathBuf),
     LeaveMenu(GameState),
 }
 
@@ -27,6 +34,7 @@ pub(super) fn menu_actions(
     mut set_menu_state: ResMut<NextState<MenuState>>,
     mut set_game_state: ResMut<NextState<GameState>>,
     mut menu_actions: MessageReader<MenuActionMessage>,
+    mut latest_save: ResMut<LatestSave>,
 ) {
     if !menu_actions.is_empty() {
         let mut ma_params = MenuActionParams {
@@ -45,6 +53,12 @@ pub(super) fn menu_actions(
                         board_cache.clone(),
                     );
                 }
+                MenuActionMessage::Continue(path) => {
+                    continue_game(&mut ma_params, &mut latest_save, path)
+                }
+                MenuActionMessage::WatchReplay(path) => {
+                    watch_replay(&mut ma_params, &mut latest_save, path)
+                }
                 MenuActionMessage::LeaveMenu(to) => leave_menu(&mut ma_params, *to),
                 MenuActionMessage::EnterNewGameMenu => enter_new_game_menu(&mut ma_params),
             }
@@ -75,6 +89,38 @@ fn start_new_game(
     leave_menu(ma_params, GameState::Game);
 }
 
+fn continue_game(ma_params: &mut MenuActionParams, latest_save: &mut LatestSave, path: &Path) {
+    match SaveGame::from_file(path) {
+        Ok(save) => {
+            let board_cache = BoardCache::new(&save.board);
+            start_new_game(
+                ma_params,
+                save.game.clone(),
+                save.board.clone(),
+                board_cache,
+            );
+            ma_params.cmds.insert_resource(save);
+        }
+        Err(err) => latest_save.err_text = Some(err.to_string()),
+    }
+}
+
+fn watch_replay(ma_params: &mut MenuActionParams, latest_save: &mut LatestSave, path: &Path) {
+    match Replay::from_file(path) {
+        Ok(replay) => {
+            let board_cache = BoardCache::new(&replay.board);
+            start_new_game(
+                ma_params,
+                replay.new_game(),
+                replay.board.clone(),
+                board_cache,
+            );
+            ma_params.cmds.insert_resource(ReplayPlayback::new(&replay));
+        }
+        Err(err) => latest_save.err_text = Some(err.to_string()),
+    }
+}
+
 fn leave_menu(ma_params: &mut MenuActionParams, to: GameState) {
     ma_params.set_menu_state.set(MenuState::Main);
     ma_params.set_game_state.set(to);
@@ -1,10 +1,12 @@
 use self::{
     actions::{menu_actions, MenuActionMessage},
     new_game_menu::{add_new_game_menu, new_game_menu_setup},
+    replays_menu::{add_replays_menu, replays_menu_setup},
 };
 use crate::{
+    game::save::latest_save_path,
     menu_panels::{controls_content, settings_content},
-    utils::GameState,
+    utils::{add_error_box, GameState},
     TITLE,
 };
 use bevy::{app::AppExit, prelude::*};
@@ -12,10 +14,12 @@ use bevy_egui::{
     egui::{self, CentralPanel, Color32, Frame, Label, Response, RichText, ScrollArea, SidePanel},
     EguiContexts, EguiPrimaryContextPass,
 };
+use std::path::PathBuf;
 
 mod actions;
 // mod controls;
 mod new_game_menu;
+mod replays_menu;
 
 const SIDE_BAR_WIDTH: f32 = 300.0;
 
@@ -25,15 +29,24 @@ enum MenuState {
     #[default]
     Main,
     NewGame,
+    Replays,
     Settings,
     Controls,
 }
 
+// Save the "Continue" entry resumes
+#[derive(Resource, Default)]
+struct LatestSave {
+    path: Option<PathBuf>,
+    err_text: Option<String>,
+}
+
 pub struct MainMenuPlugin;
 
 impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<MenuActionMessage>()
+            .add_systems(OnEnter(GameState::Menu), find_latest_save)
             .add_systems(
                 EguiPrimaryContextPass,
                 startup_menu.run_if(in_state(GameState::Menu)),
@@ -44,10 +57,22 @@ impl Plugin for MainMenuPlugin {
                 EguiPrimaryContextPass,
                 (add_new_game_menu.after(startup_menu)).run_if(in_state(MenuState::NewGame)),
             )
+            .add_systems(OnEnter(MenuState::Replays), replays_menu_setup)
+            .add_systems(
+                EguiPrimaryContextPass,
+                (add_replays_menu.after(startup_menu)).run_if(in_state(MenuState::Replays)),
+            )
             .init_state::<MenuState>();
     }
 }
 
+fn find_latest_save(mut cmds: Commands) {
+    cmds.insert_resource(LatestSave {
+        path: latest_save_path(),
+        err_text: None,
+    });
+}
+
 fn startup_menu(
     mut set_menu_state: ResMut<NextState<MenuState>>,
     menu_state: Res<State<MenuState>>,
@@ -55,8 +80,10 @@ fn startup_menu(
     mut app_exit_events: MessageWriter<AppExit>,
     actions: MessageWriter<MenuActionMessage>,
     settings: ResMut<crate::user::Settings>,
+    latest_save: Res<LatestSave>,
 ) {
     add_main_menu(
+        &latest_save,
         &menu_state,
         &mut set_menu_state,
         &mut egui_ctx,
@@ -74,6 +101,7 @@ fn startup_menu(
 }
 
 fn add_main_menu(
+    latest_save: &LatestSave,
     menu_state: &State<MenuState>,
     set_menu_state: &mut NextState<MenuState>,
     egui_ctx: &mut EguiContexts,
@@ -92,10 +120,26 @@ fn add_main_menu(
                 Label::new(RichText::new(TITLE).heading()),
             );
 
+            if let Some(path) = &latest_save.path {
+                if add_menu_button("Continue", ui).clicked() {
+                    actions.write(MenuActionMessage::Continue(path.clone()));
+                }
+            }
+            if let Some(err_text) = &latest_save.err_text {
+                add_error_box(err_text, ui);
+            }
+
             if add_menu_button("Play", ui).clicked() {
                 actions.write(MenuActionMessage::EnterNewGameMenu);
             }
 
+            if add_menu_button("Replays", ui).clicked() {
+                match **menu_state {
+                    MenuState::Replays => set_menu_state.set(MenuState::Main),
+                    _ => set_menu_state.set(MenuState::Replays),
+                }
+            }
+
             if add_menu_button("Map Editor", ui).clicked() {
                 actions.write(MenuActionMessage::LeaveMenu(GameState::MapEditor));
             }
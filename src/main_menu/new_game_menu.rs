@@ -1,8 +1,12 @@
 use super::actions::MenuActionMessage;
 use crate::{
-    board::{Board, BoardCache},
+    board::{
+        generator::{generate_board, GeneratorParams},
+        map_file::{get_all_boards_in_folder, MapFolder},
+        Board, BoardCache,
+    },
     game::Game,
-    utils::{add_error_box, get_all_boards_in_folder, Difficulty},
+    utils::{add_error_box, random_seed, Difficulty, Seed},
 };
 use bevy::prelude::*;
 use bevy_egui::{
@@ -16,29 +20,59 @@ pub(super) struct NewGameMenu {
     boards: Vec<(Board, BoardCache)>,
     selected_board_index: usize,
     difficulty: Difficulty,
+    seed_text: String,
     err_text: Option<String>,
+    // Maps that fail validation, shown with the reason instead of silently hidden
+    unplayable_text: Option<String>,
 }
 
+const RANDOM_MAP_TEXT: &str = "Random map (from seed)";
+
 impl NewGameMenu {
+    // Index after the last map selects a generated one
     fn selected_board(&self) -> Option<&(Board, BoardCache)> {
         self.boards.get(self.selected_board_index)
     }
 
-    fn new(boards: Vec<Board>) -> Self {
+    fn selected_board_name(&self) -> &str {
+        match self.selected_board() {
+            Some((board, _)) => &board.name,
+            None => RANDOM_MAP_TEXT,
+        }
+    }
+
+    fn board_to_play(&self, seed: Seed) -> Result<(Board, BoardCache), Box<dyn Error>> {
+        if let Some(selected) = self.selected_board() {
+            return Ok(selected.clone());
+        }
+        let board = generate_board(&GeneratorParams::default(), seed)?;
+        let board_cache = BoardCache::new(&board);
+        Ok((board, board_cache))
+    }
+
+    fn new(folder: MapFolder) -> Self {
+        let err_text = folder.err_text();
+        let mut boards = Vec::new();
+        let mut unplayable = Vec::new();
+        for board in folder.boards {
+            let cache = BoardCache::new(&board);
+            match cache.validate() {
+                Ok(_) => boards.push((board, cache)),
+                Err(errors) => {
+                    unplayable.push(format!("'{}' is not playable:\n{errors}", board.name))
+                }
+            }
+        }
         Self {
-            boards: boards
-                .into_iter()
-                .filter_map(|board| {
-                    let cache = BoardCache::new(&board);
-                    if cache.validate().is_ok() {
-                        return Some((board, cache));
-                    }
-                    None
-                })
-                .collect(),
+            err_text,
+            boards,
             selected_board_index: 0,
             difficulty: Difficulty::Easy,
-            err_text: None,
+            seed_text: random_seed().to_string(),
+            unplayable_text: match unplayable.is_empty() {
+                true => None,
+                false => Some(unplayable.join("\n\n")),
+            },
         }
     }
 
@@ -47,7 +81,9 @@ impl NewGameMenu {
             boards: Vec::new(),
             selected_board_index: 0,
             difficulty: Difficulty::Easy,
+            seed_text: random_seed().to_string(),
             err_text: Some(err.to_string()),
+            unplayable_text: None,
         }
     }
 }
@@ -55,7 +91,7 @@ impl NewGameMenu {
 impl Default for NewGameMenu {
     fn default() -> Self {
         match get_all_boards_in_folder() {
-            Ok(boards) => Self::new(boards),
+            Ok(folder) => Self::new(folder),
             Err(err) => Self::new_error(err),
         }
     }
@@ -82,6 +118,11 @@ pub(super) fn add_new_game_menu(
             }
             board_select(ui, &mut new_game_menu);
             difficulty_select(ui, &mut new_game_menu);
+            seed_select(ui, &mut new_game_menu);
+
+            if let Some(unplayable_text) = &new_game_menu.unplayable_text {
+                ui.collapsing("Unplayable maps", |ui| add_error_box(unplayable_text, ui));
+            }
         });
         bottom_panel(ui, &mut new_game_menu, actions);
     });
@@ -90,20 +131,20 @@ pub(super) fn add_new_game_menu(
 fn board_select(ui: &mut egui::Ui, new_game_menu: &mut NewGameMenu) {
     ui.horizontal(|ui| {
         ui.add_sized([200., 60.], bevy_egui::egui::Label::new("Map"));
-        if let Some(selected) = new_game_menu.selected_board() {
-            egui::containers::ComboBox::from_label("")
-                .selected_text(&selected.0.name)
-                .show_ui(ui, |ui| {
-                    ui.set_width(400.);
-                    let boards = &new_game_menu.boards;
-                    let mut selected_i = new_game_menu.selected_board_index;
-
-                    for (i, board) in boards.iter().enumerate() {
-                        ui.selectable_value(&mut selected_i, i, &board.0.name);
-                    }
-                    new_game_menu.selected_board_index = selected_i;
-                });
-        } else {
+        egui::containers::ComboBox::from_label("")
+            .selected_text(new_game_menu.selected_board_name())
+            .show_ui(ui, |ui| {
+                ui.set_width(400.);
+                let boards = &new_game_menu.boards;
+                let mut selected_i = new_game_menu.selected_board_index;
+
+                for (i, board) in boards.iter().enumerate() {
+                    ui.selectable_value(&mut selected_i, i, &board.0.name);
+                }
+                ui.selectable_value(&mut selected_i, boards.len(), RANDOM_MAP_TEXT);
+                new_game_menu.selected_board_index = selected_i;
+            });
+        if new_game_menu.boards.is_empty() {
             ui.add_sized(
                 [200., 60.],
                 bevy_egui::egui::Label::new("No valid maps found."),
@@ -119,6 +160,22 @@ fn difficulty_select(ui: &mut egui::Ui, new_game_menu: &mut NewGameMenu) {
     });
 }
 
+fn seed_select(ui: &mut egui::Ui, new_game_menu: &mut NewGameMenu) {
+    ui.horizontal(|ui| {
+        ui.add_sized([200., 60.], bevy_egui::egui::Label::new("Seed"));
+        ui.add_sized(
+            [200., 30.],
+            egui::TextEdit::singleline(&mut new_game_menu.seed_text),
+        );
+        if ui
+            .add_sized([200., 60.], egui::widgets::Button::new("Random"))
+            .clicked()
+        {
+            new_game_menu.seed_text = random_seed().to_string();
+        }
+    });
+}
+
 fn bottom_panel(
     ui: &mut egui::Ui,
     new_game_menu: &mut NewGameMenu,
@@ -148,13 +205,22 @@ fn play_button(
         .add_sized([400., 60.], bevy_egui::egui::widgets::Button::new("Play"))
         .clicked()
     {
-        let (board, board_cache) = new_game_menu
-            .boards
-            .get(new_game_menu.selected_board_index)
-            .unwrap()
-            .clone();
+        let Ok(seed) = new_game_menu.seed_text.trim().parse::<Seed>() else {
+            new_game_menu.err_text = Some(format!(
+                "Seed must be a positive number, not '{}'",
+                new_game_menu.seed_text
+            ));
+            return;
+        };
+        let (board, board_cache) = match new_game_menu.board_to_play(seed) {
+            Ok(board) => board,
+            Err(err) => {
+                new_game_menu.err_text = Some(err.to_string());
+                return;
+            }
+        };
         actions.write(MenuActionMessage::StartNewGame(
-            Game::new(new_game_menu.difficulty),
+            Game::new(new_game_menu.difficulty, seed),
             board,
             board_cache,
         ));
@@ -0,0 +1,82 @@
+use super::actions::MenuActionMessage;
+use crate::{
+    game::replay::{get_all_replays_in_folder, Replay},
+    utils::add_error_box,
+};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, CentralPanel, ScrollArea},
+    EguiContexts,
+};
+use std::path::PathBuf;
+
+#[derive(Resource)]
+pub(super) struct ReplaysMenu {
+    replays: Vec<(PathBuf, Replay)>,
+    err_text: Option<String>,
+}
+
+impl Default for ReplaysMenu {
+    fn default() -> Self {
+        match get_all_replays_in_folder() {
+            Ok(mut folder) => {
+                folder.replays.sort_by(|(a, _), (b, _)| b.cmp(a));
+                Self {
+                    err_text: folder.err_text(),
+                    replays: folder.replays,
+                }
+            }
+            Err(err) => Self {
+                replays: Vec::new(),
+                err_text: Some(err.to_string()),
+            },
+        }
+    }
+}
+
+pub(super) fn replays_menu_setup(mut commands: Commands) {
+    commands.init_resource::<ReplaysMenu>();
+}
+
+pub(super) fn add_replays_menu(
+    mut egui_ctx: EguiContexts,
+    replays_menu: Res<ReplaysMenu>,
+    mut actions: MessageWriter<MenuActionMessage>,
+) {
+    let Ok(ctx) = egui_ctx.ctx_mut() else { return };
+    CentralPanel::default().show(ctx, |ui| {
+        ui.set_height(ui.available_height());
+        ui.vertical_centered(|ui| {
+            ui.heading("Replays");
+
+            // Error container
+            if let Some(err_text) = &replays_menu.err_text {
+                add_error_box(err_text, ui);
+            }
+            if replays_menu.replays.is_empty() {
+                ui.add_sized([200., 60.], egui::Label::new("No replays found."));
+            }
+        });
+        ScrollArea::vertical().show(ui, |ui| {
+            for (path, replay) in replays_menu.replays.iter() {
+                if replay_row(ui, replay) {
+                    actions.write(MenuActionMessage::WatchReplay(path.clone()));
+                }
+            }
+        });
+    });
+}
+
+fn replay_row(ui: &mut egui::Ui, replay: &Replay) -> bool {
+    ui.horizontal(|ui| {
+        ui.add_sized([300., 60.], egui::Label::new(&replay.board.name));
+        ui.add_sized([200., 60.], egui::Label::new(replay.difficulty.to_string()));
+        ui.add_sized(
+            [200., 60.],
+            egui::Label::new(format!("Seed {}", replay.seed)),
+        );
+        ui.add_sized([200., 60.], egui::widgets::Button::new("Watch"))
+            .clicked()
+    })
+    .inner
+}
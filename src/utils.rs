@@ -4,6 +4,7 @@ use crate::{CamMutQuery, CamQuery};
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 use euclid::Angle;
+pub use game_rng::{random_seed, GameRng, Seed};
 pub use ingame_time::IngameTime;
 pub use ingame_time::IngameTimestamp;
 use serde::{Deserialize, Serialize};
@@ -12,15 +13,19 @@ pub use vec2_board::Vec2Board;
 pub mod buffer;
 pub mod buildings;
 pub mod collision;
+pub mod damage;
 pub mod energy;
 pub mod explosions;
+mod game_rng;
 pub mod health_bar;
 mod ingame_time;
+pub mod interpolation;
 pub mod materials;
 pub mod range_circle;
 pub mod resource_bar;
 pub mod shots;
 pub mod speed;
+pub mod status_effects;
 pub mod towers;
 mod vec2_board;
 pub mod wave;
@@ -40,7 +45,18 @@ pub enum GameState {
     MapEditor,
 }
 
-#[derive(strum::EnumIter, strum::Display, PartialEq, Eq, Clone, Copy)]
+#[derive(
+    strum::EnumIter,
+    strum::Display,
+    strum::EnumString,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Debug,
+    Serialize,
+    Deserialize,
+)]
 pub enum Difficulty {
     Easy,
     Middle,
@@ -65,37 +81,6 @@ pub fn despawn_all_of<T: Component>(to_despawn: Query<Entity, With<T>>, mut comm
 
 use crate::assets::{FONT_QUICKSAND, FONT_SCREEN_DIAGS};
 use bevy::camera::ScalingMode;
-use std::error::Error;
-use std::fs::{read_dir, read_to_string, DirEntry, File};
-use std::io::Write;
-use std::path::Path;
-
-pub fn save_board_to_file(name: &str, board: &Board) -> Result<(), Box<dyn Error>> {
-    let mut output = File::create(Path::new(&format!("./maps/{}_map.json", name)))?;
-    write!(output, "{}", serde_json::to_string(board)?)?;
-    Ok(())
-}
-
-pub fn get_all_boards_in_folder() -> Result<Vec<Board>, Box<dyn Error>> {
-    let mut boards = Vec::<Board>::new();
-    for dir_entry in read_dir("./maps/")? {
-        boards.push(board_from_file(dir_entry)?);
-    }
-    Ok(boards)
-}
-
-fn board_from_file(dir_entry: Result<DirEntry, std::io::Error>) -> Result<Board, Box<dyn Error>> {
-    let dir_entry = dir_entry?;
-    match serde_json::from_str(&read_to_string(dir_entry.path())?) {
-        Ok(board) => Ok(board),
-        Err(err) => Err(format!(
-            "Invalid json in file '{:?}': {}",
-            dir_entry.file_name(),
-            err
-        )
-        .into()),
-    }
-}
 
 pub fn add_row(label: &str, widget: impl bevy_egui::egui::Widget, ui: &mut bevy_egui::egui::Ui) {
     let width_right_col = ui.available_width() - 200.0;
@@ -182,13 +167,10 @@ pub fn cursor_pos(q_win: Query<&Window>, q_cam: CamQuery) -> Option<Vec2Board> {
         return None;
     };
     let Ok(win) = q_win.single() else { return None };
-    let Some(screen_pos) = win
+    let screen_pos = win
         .cursor_position()
-        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
-    else {
-        return None;
-    };
-    return Some((screen_pos / TILE_SIZE).into());
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())?;
+    Some((screen_pos / TILE_SIZE).into())
 }
 
 pub fn text_bundle(
@@ -256,3 +238,16 @@ pub fn visible(is_visible: bool) -> Visibility {
         false => Visibility::Hidden,
     }
 }
+
+// Names come from the editor and imported maps, anything but letters, digits,
+// spaces, '-' and '_' becomes '_' so the name can't leave its folder
+pub fn file_name_of(name: &str) -> String {
+    name.chars()
+        .map(
+            |c| match c.is_alphanumeric() || matches!(c, ' ' | '-' | '_') {
+                true => c,
+                false => '_',
+            },
+        )
+        .collect()
+}
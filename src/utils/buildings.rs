@@ -23,7 +23,7 @@ pub struct BuildingValues {}
 //}
 //}
 
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Building {
     PowerPlant,
     Factory,
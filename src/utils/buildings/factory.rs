@@ -8,7 +8,7 @@ use crate::{
     },
     utils::{
         buffer::Buffer, materials::MATERIALS_COLOR, resource_bar::spawn_resource_bar, Amount,
-        BoardPos, Energy, Materials, Vec2Board,
+        BoardPos, Energy, IngameTimestamp, Materials, Vec2Board,
     },
 };
 use bevy::color::palettes::css::{DIM_GRAY, GRAY};
@@ -22,6 +22,7 @@ pub struct Factory {
     pub pos: Vec2Board,
     pub enery: Buffer<Energy>,
     pub materials: Buffer<Materials>,
+    pub built_at: IngameTimestamp,
 }
 
 impl Factory {
@@ -36,6 +37,7 @@ impl Factory {
                 FACTORY_MATERIALS_BUFFER_SIZE,
                 Amount::PerSecond(FACTORY_MATERIALS_PER_SECOND),
             ),
+            built_at: IngameTimestamp::default(),
         }
     }
 
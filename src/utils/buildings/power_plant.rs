@@ -3,7 +3,7 @@ use crate::{
     balance::{POWER_PLANT_BUFFER_SIZE, POWER_PLANT_ENERGY_PER_SECOND},
     utils::{
         buffer::Buffer, energy::ENERGY_COLOR, resource_bar::spawn_resource_bar, Amount, BoardPos,
-        Energy, Vec2Board,
+        Energy, IngameTimestamp, Vec2Board,
     },
 };
 use bevy::color::palettes::css::{DIM_GRAY, GRAY};
@@ -16,6 +16,7 @@ use std::time::Duration;
 pub struct PowerPlant {
     pub pos: Vec2Board,
     pub energy: Buffer<Energy>,
+    pub built_at: IngameTimestamp,
 }
 
 impl PowerPlant {
@@ -26,6 +27,7 @@ impl PowerPlant {
                 POWER_PLANT_BUFFER_SIZE,
                 Amount::PerSecond(POWER_PLANT_ENERGY_PER_SECOND),
             ),
+            built_at: IngameTimestamp::default(),
         }
     }
 
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, Display, EnumIter,
+)]
+pub enum DamageType {
+    // Lasers
+    #[default]
+    Energy,
+    // Rockets, grenades and death explosions
+    Explosive,
+    Microwave,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Damage {
+    pub amount: f32,
+    pub kind: DamageType,
+    // Share of the resistance which gets ignored, between 0 and 1
+    pub armor_piercing: f32,
+}
+
+impl Damage {
+    pub fn new(amount: f32, kind: DamageType) -> Self {
+        Self {
+            amount,
+            kind,
+            armor_piercing: 0.,
+        }
+    }
+
+    pub fn scaled(mut self, factor: f32) -> Self {
+        self.amount *= factor;
+        self
+    }
+
+    pub fn with_armor_piercing(mut self, armor_piercing: f32) -> Self {
+        self.armor_piercing = armor_piercing;
+        self
+    }
+}
+
+// Share of the damage an enemy ignores per damage type, negative values are weaknesses
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Resistances {
+    #[serde(default)]
+    pub energy: f32,
+    #[serde(default)]
+    pub explosive: f32,
+    #[serde(default)]
+    pub microwave: f32,
+}
+
+impl Resistances {
+    pub fn get(&self, kind: DamageType) -> f32 {
+        match kind {
+            DamageType::Energy => self.energy,
+            DamageType::Explosive => self.explosive,
+            DamageType::Microwave => self.microwave,
+        }
+    }
+
+    // Damage left after the resistance, armor piercing doesn't weaken weaknesses
+    pub fn apply(&self, damage: &Damage) -> f32 {
+        let resistance = self.get(damage.kind).min(1.);
+        let resistance = match resistance > 0. {
+            true => resistance * (1. - damage.armor_piercing.clamp(0., 1.)),
+            false => resistance,
+        };
+        damage.amount * (1. - resistance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_armor_piercing_ignores_part_of_the_resistance() {
+        let resistances = Resistances {
+            energy: 0.5,
+            microwave: -0.5,
+            ..Default::default()
+        };
+        let laser = Damage::new(100., DamageType::Energy);
+        assert_eq!(resistances.apply(&laser), 50.);
+        assert_eq!(resistances.apply(&laser.with_armor_piercing(0.5)), 75.);
+        let microwave = Damage::new(100., DamageType::Microwave).with_armor_piercing(1.);
+        assert_eq!(resistances.apply(&microwave), 150.);
+        assert_eq!(
+            resistances.apply(&Damage::new(100., DamageType::Explosive)),
+            100.
+        );
+    }
+}
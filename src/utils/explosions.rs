@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use super::{TilesPerSecond, Vec2Board};
+use super::{damage::Damage, TilesPerSecond, Vec2Board};
 use crate::board::visualisation::TILE_SIZE;
 use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
@@ -12,11 +12,11 @@ pub struct Explosion {
     pub pos: Vec2Board,
     pub target_radius: f32,
     pub current_radius: f32,
-    pub damage: f32,
+    pub damage: Damage,
 }
 
 impl Explosion {
-    pub fn new(pos: Vec2Board, radius: f32, damage: f32) -> Self {
+    pub fn new(pos: Vec2Board, radius: f32, damage: Damage) -> Self {
         Self {
             pos,
             target_radius: radius,
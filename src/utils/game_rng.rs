@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+use rand::{rngs::Xoshiro256PlusPlus, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+pub type Seed = u64;
+
+// The only source of randomness for gameplay, so a seed replays the same match
+#[derive(Resource, Deref, DerefMut, Clone, Debug, Serialize, Deserialize)]
+pub struct GameRng(Xoshiro256PlusPlus);
+
+impl GameRng {
+    pub fn new(seed: Seed) -> Self {
+        Self(Xoshiro256PlusPlus::seed_from_u64(seed))
+    }
+}
+
+// Kept short, so it's easy to read out and type in again
+pub fn random_seed() -> Seed {
+    rand::random::<u32>() as Seed
+}
@@ -13,6 +13,15 @@ pub struct IngameTime {
 }
 
 impl IngameTime {
+    pub fn from_elapsed(elapsed: Duration) -> Self {
+        let mut watch = Stopwatch::new();
+        watch.set_elapsed(elapsed);
+        Self {
+            watch,
+            delta: Duration::ZERO,
+        }
+    }
+
     pub fn now(&self) -> IngameTimestamp {
         self.watch.elapsed_secs().into()
     }
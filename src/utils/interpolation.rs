@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+// Translation of the previous and current simulation tick.
+// Rendering blends between both, so movement looks smooth at any frame rate.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct TickTranslation {
+    previous: Vec3,
+    pub current: Vec3,
+}
+
+impl TickTranslation {
+    pub fn new(translation: Vec3) -> Self {
+        Self {
+            previous: translation,
+            current: translation,
+        }
+    }
+
+    pub fn lerp(&self, tick_fraction: f32) -> Vec3 {
+        self.previous.lerp(self.current, tick_fraction)
+    }
+}
+
+// Runs before every simulation tick
+pub fn begin_tick_translation_system(mut q_translations: Query<&mut TickTranslation>) {
+    q_translations
+        .iter_mut()
+        .for_each(|mut translation| translation.previous = translation.current);
+}
+
+pub fn interpolation_system(
+    mut q_transforms: Query<(&mut Transform, &TickTranslation)>,
+    time: Res<Time<Fixed>>,
+) {
+    let tick_fraction = time.overstep_fraction();
+    q_transforms
+        .iter_mut()
+        .for_each(|(mut transform, translation)| {
+            transform.translation = translation.lerp(tick_fraction)
+        });
+}
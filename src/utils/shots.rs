@@ -1,14 +1,17 @@
-use crate::board::step::BoardStep;
+use crate::board::step::RoadPath;
 
 use super::buffer::Buffer;
+use super::damage::DamageType;
 use super::range_circle::RangeCircle;
-use super::{IngameTimestamp, Materials, TilesPerSecond, Vec2Board};
+use super::{Energy, IngameTimestamp, Materials, TilesPerSecond, Vec2Board};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::ops::{Deref, DerefMut};
 use std::time::Duration;
 
+pub mod grenade;
 pub mod laser;
+pub mod microwave;
 pub mod rocket;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -22,11 +25,50 @@ pub enum TowerStatus {
 pub enum Shot {
     Laser(DamagePerTimeShotValues),
     Rocket(DamageInRadiusTargetPosShotValues),
+    Microwave(SlowInRadiusShotValues),
+    Grenade(BallisticShotValues),
+}
+
+impl Shot {
+    // Targets closer than this can't be hit
+    pub fn min_range_radius(&self) -> f32 {
+        match self {
+            Shot::Grenade(shot) => shot.min_range_radius,
+            _ => 0.,
+        }
+    }
+
+    pub fn damage(&self) -> f32 {
+        match self {
+            Shot::Laser(shot) => shot.damage,
+            Shot::Rocket(shot) => shot.damage,
+            Shot::Microwave(shot) => shot.damage,
+            Shot::Grenade(shot) => shot.damage,
+        }
+    }
+
+    // Beams and pulses also last longer, rockets and grenades only hit harder
+    pub fn upgrade(&mut self, damage_factor: f32, duration_factor: f32) {
+        match self {
+            Shot::Laser(shot) => {
+                shot.damage *= damage_factor;
+                shot.lifetime = shot.lifetime.mul_f32(duration_factor);
+            }
+            Shot::Rocket(shot) => shot.damage *= damage_factor,
+            Shot::Microwave(shot) => {
+                shot.damage *= damage_factor;
+                shot.lifetime = shot.lifetime.mul_f32(duration_factor);
+            }
+            Shot::Grenade(shot) => shot.damage *= damage_factor,
+        }
+    }
 }
 
 #[derive(Component, Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DamagePerTimeShotValues {
     pub damage: f32,
+    pub damage_type: DamageType,
+    pub armor_piercing: f32,
     pub lifetime: Duration,
     pub pos_start: Vec2Board,
     pub range_radius: f32,
@@ -53,11 +95,103 @@ impl DamagePerTimeShotValues {
     }
 }
 
+// Pulses hit every enemy in range, no target lock
+#[derive(Component, Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SlowInRadiusShotValues {
+    pub pos_start: Vec2Board,
+    pub range_radius: f32,
+    pub damage: f32,
+    pub damage_type: DamageType,
+    pub slow_factor: f32,
+    pub slow_duration: Duration,
+    pub pulse_interval: Duration,
+    pub lifetime: Duration,
+    // Drained while the tower fires
+    pub energy: Buffer<Energy>,
+}
+
+#[derive(Component, Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SlowInRadiusShot {
+    pub next_pulse: IngameTimestamp,
+    pub die_time: IngameTimestamp,
+    pub vals: SlowInRadiusShotValues,
+}
+
+impl SlowInRadiusShotValues {
+    pub fn new_shot(&self, now: IngameTimestamp) -> SlowInRadiusShot {
+        SlowInRadiusShot {
+            next_pulse: now,
+            die_time: now + self.lifetime,
+            vals: self.clone(),
+        }
+    }
+}
+
+// Lobbed to a fixed ground position, it doesn't follow the enemy
+#[derive(Component, Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BallisticShotValues {
+    pub pos_start: Vec2Board,
+    pub damage: f32,
+    pub damage_type: DamageType,
+    pub armor_piercing: f32,
+    pub damage_radius: f32,
+    pub range_radius: f32,
+    pub min_range_radius: f32,
+    pub flight_duration: Duration,
+    pub arc_height: f32,
+    pub materials_per_shot: Materials,
+}
+
+#[derive(Component, Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BallisticShot {
+    pub target_pos: Vec2Board,
+    pub launch_time: IngameTimestamp,
+    pub pos: Vec2Board,
+    pub vals: BallisticShotValues,
+}
+
+impl BallisticShotValues {
+    pub fn new_shot(&self, target_pos: Vec2Board, now: IngameTimestamp) -> BallisticShot {
+        BallisticShot {
+            target_pos,
+            launch_time: now,
+            pos: self.pos_start,
+            vals: self.clone(),
+        }
+    }
+}
+
+impl BallisticShot {
+    // Share of the flight done, 1 on landing
+    pub fn progress(&self, now: IngameTimestamp) -> f32 {
+        ((*now - *self.launch_time) / self.flight_duration.as_secs_f32()).clamp(0., 1.)
+    }
+
+    pub fn fly(&mut self, now: IngameTimestamp) {
+        let start: Vec2 = self.pos_start.into();
+        self.pos = start
+            .lerp(self.target_pos.into(), self.progress(now))
+            .into();
+    }
+
+    // Parabola over the ground position, highest in the middle of the flight
+    pub fn height(&self, now: IngameTimestamp) -> f32 {
+        let progress = self.progress(now);
+        4. * self.arc_height * progress * (1. - progress)
+    }
+
+    pub fn is_landed(&self, now: IngameTimestamp) -> bool {
+        self.progress(now) >= 1.
+    }
+}
+
 #[derive(Component, Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DamageInRadiusTargetPosShotValues {
     pub pos_start: Vec2Board,
     pub pos: Vec2Board,
     pub damage: f32,
+    pub damage_type: DamageType,
+    pub armor_piercing: f32,
     pub damage_radius: f32,
     pub range_radius: f32,
     pub speed: TilesPerSecond,
@@ -85,14 +219,22 @@ impl DamageInRadiusTargetPosShot {
         self.fuel.fill -= distance_walked;
     }
 
-    pub fn set_target_point_to_likely(&mut self, road_path: &[BoardStep]) {
+    // Earliest reachable road point of every route, the nearest one wins
+    pub fn set_target_point_to_likely<'a>(
+        &mut self,
+        road_paths: impl Iterator<Item = &'a RoadPath>,
+    ) {
         self.target_id = None;
         let range = RangeCircle::new(self.pos, self.fuel.fill);
-        let posis = road_path.iter().map(|step| step.start_pos);
-        if let Some(pos) = posis
-            .clone()
-            .zip(posis.skip(1))
-            .find_map(|(vec_start, vec_end)| range.target_point(*vec_start, *vec_end))
+        if let Some(pos) = road_paths
+            .filter_map(|road_path| {
+                let posis = road_path.iter().map(|step| step.start_pos);
+                posis
+                    .clone()
+                    .zip(posis.skip(1))
+                    .find_map(|(vec_start, vec_end)| range.target_point(*vec_start, *vec_end))
+            })
+            .min_by(|a, b| a.distance(*self.pos).total_cmp(&b.distance(*self.pos)))
         {
             self.target_pos = pos.into();
         }
@@ -129,6 +271,22 @@ impl DerefMut for DamagePerTimeShot {
     }
 }
 
+impl Deref for SlowInRadiusShot {
+    type Target = SlowInRadiusShotValues;
+
+    fn deref(&self) -> &Self::Target {
+        &self.vals
+    }
+}
+
+impl Deref for BallisticShot {
+    type Target = BallisticShotValues;
+
+    fn deref(&self) -> &Self::Target {
+        &self.vals
+    }
+}
+
 impl Deref for DamageInRadiusTargetPosShot {
     type Target = DamageInRadiusTargetPosShotValues;
 
@@ -142,3 +300,30 @@ impl DerefMut for DamageInRadiusTargetPosShot {
         &mut self.vals
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ballistic_shot_lands_on_target() {
+        let vals = match Shot::grenade(Vec2Board::new(1., 1.)) {
+            Shot::Grenade(vals) => vals,
+            _ => unreachable!(),
+        };
+        let flight_secs = vals.flight_duration.as_secs_f32();
+        let mut shot = vals.new_shot(Vec2Board::new(3., 1.), IngameTimestamp::new(10.));
+
+        let half_way = IngameTimestamp::new(10. + flight_secs / 2.);
+        shot.fly(half_way);
+        assert!(shot.pos.distance(Vec2::new(2., 1.)) < 0.001);
+        assert!((shot.height(half_way) - shot.arc_height).abs() < 0.001);
+        assert!(!shot.is_landed(half_way));
+
+        let landing = IngameTimestamp::new(10. + flight_secs + 0.01);
+        shot.fly(landing);
+        assert_eq!(shot.pos, shot.target_pos);
+        assert_eq!(shot.height(landing), 0.);
+        assert!(shot.is_landed(landing));
+    }
+}
@@ -0,0 +1,68 @@
+use super::{BallisticShot, BallisticShotValues, Shot};
+use crate::{
+    balance::{
+        GRENADE_ARC_HEIGHT_TILES, GRENADE_ARMOR_PIERCING, GRENADE_DAMAGE, GRENADE_DAMAGE_RADIUS,
+        GRENADE_FLIGHT_DURATION_SECS, GRENADE_MATERIALS_PER_SHOT, GRENADE_MIN_RANGE_RADIUS,
+        GRENADE_RANGE_RADIUS,
+    },
+    board::visualisation::TILE_SIZE,
+    utils::{damage::DamageType, interpolation::TickTranslation, IngameTimestamp, Vec2Board},
+};
+use bevy::color::palettes::css::{DARK_OLIVEGREEN, DIM_GRAY};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use std::time::Duration;
+
+pub const INIT_RANGE_RADIUS: f32 = GRENADE_RANGE_RADIUS;
+const SHOT_Z: f32 = 3.;
+
+#[derive(Component)]
+pub struct GrenadeShot;
+
+impl Shot {
+    pub fn grenade(pos_start: Vec2Board) -> Self {
+        Self::Grenade(BallisticShotValues {
+            pos_start,
+            damage: GRENADE_DAMAGE,
+            damage_type: DamageType::Explosive,
+            armor_piercing: GRENADE_ARMOR_PIERCING,
+            damage_radius: GRENADE_DAMAGE_RADIUS,
+            range_radius: INIT_RANGE_RADIUS,
+            min_range_radius: GRENADE_MIN_RANGE_RADIUS,
+            flight_duration: Duration::from_secs_f32(GRENADE_FLIGHT_DURATION_SECS),
+            arc_height: GRENADE_ARC_HEIGHT_TILES,
+            materials_per_shot: GRENADE_MATERIALS_PER_SHOT,
+        })
+    }
+}
+
+impl BallisticShot {
+    // The arc is drawn as an offset upwards on the board
+    pub fn scaled_pos(&self, now: IngameTimestamp) -> Vec3 {
+        self.pos.to_scaled_vec3(SHOT_Z) + Vec3::Y * self.height(now) * TILE_SIZE
+    }
+}
+
+pub fn spawn_shot_grenade<TScreen: Component + Default>(
+    cmds: &mut Commands,
+    shot: BallisticShot,
+    now: IngameTimestamp,
+) {
+    let translation = shot.scaled_pos(now);
+    cmds.spawn(grenade_shape())
+        .insert(Transform::from_translation(translation))
+        .insert(TickTranslation::new(translation))
+        .insert(shot)
+        .insert(GrenadeShot)
+        .insert(TScreen::default());
+}
+
+fn grenade_shape() -> impl Bundle {
+    (ShapeBuilder::with(&shapes::Circle {
+        center: Vec2::default(),
+        radius: TILE_SIZE / 12.,
+    })
+    .fill(DARK_OLIVEGREEN)
+    .stroke(Stroke::new(DIM_GRAY, TILE_SIZE / 32.))
+    .build(),)
+}
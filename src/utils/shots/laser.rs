@@ -1,8 +1,10 @@
 use super::{DamagePerTimeShot, DamagePerTimeShotValues, Shot};
 use crate::{
-    balance::{LASER_DAMAGE_PER_SECOND, LASER_RANGE_RADIUS, LASER_SHOT_DURATION_SECS},
+    balance::{
+        LASER_ARMOR_PIERCING, LASER_DAMAGE_PER_SECOND, LASER_RANGE_RADIUS, LASER_SHOT_DURATION_SECS,
+    },
     board::visualisation::TILE_SIZE,
-    utils::Vec2Board,
+    utils::{damage::DamageType, Vec2Board},
 };
 use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
@@ -18,6 +20,8 @@ impl Shot {
     pub fn laser_vals(pos_start: Vec2Board) -> Self {
         Self::Laser(DamagePerTimeShotValues {
             damage: LASER_DAMAGE_PER_SECOND,
+            damage_type: DamageType::Energy,
+            armor_piercing: LASER_ARMOR_PIERCING,
             lifetime: Duration::from_secs_f32(INIT_SHOT_DURATION_SECS),
             range_radius: INIT_RANGE_RADIUS,
             pos_start,
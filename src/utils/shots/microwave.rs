@@ -0,0 +1,82 @@
+use super::{Shot, SlowInRadiusShot, SlowInRadiusShotValues};
+use crate::{
+    balance::{
+        MICROWAVE_DAMAGE_PER_PULSE, MICROWAVE_ENERGY_PACKAGE, MICROWAVE_ENERGY_PER_SECOND,
+        MICROWAVE_PULSE_INTERVAL_SECS, MICROWAVE_RANGE_RADIUS, MICROWAVE_SHOT_DURATION_SECS,
+        MICROWAVE_SLOW_DURATION_SECS, MICROWAVE_SLOW_FACTOR,
+    },
+    board::visualisation::TILE_SIZE,
+    utils::{buffer::Buffer, damage::DamageType, Amount, Energy, Vec2Board},
+};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use std::time::Duration;
+
+pub const INIT_RANGE_RADIUS: f32 = MICROWAVE_RANGE_RADIUS;
+pub const INIT_SHOT_DURATION_SECS: f32 = MICROWAVE_SHOT_DURATION_SECS;
+
+#[derive(Component)]
+pub struct MicrowaveShot;
+
+impl Shot {
+    pub fn microwave(pos_start: Vec2Board) -> Self {
+        Self::Microwave(SlowInRadiusShotValues {
+            pos_start,
+            range_radius: INIT_RANGE_RADIUS,
+            damage: MICROWAVE_DAMAGE_PER_PULSE,
+            damage_type: DamageType::Microwave,
+            slow_factor: MICROWAVE_SLOW_FACTOR,
+            slow_duration: Duration::from_secs_f32(MICROWAVE_SLOW_DURATION_SECS),
+            pulse_interval: Duration::from_secs_f32(MICROWAVE_PULSE_INTERVAL_SECS),
+            lifetime: Duration::from_secs_f32(INIT_SHOT_DURATION_SECS),
+            energy: Buffer::<Energy>::new(
+                MICROWAVE_ENERGY_PACKAGE,
+                Amount::PerSecond(MICROWAVE_ENERGY_PER_SECOND),
+            ),
+        })
+    }
+}
+
+pub fn spawn_shot_microwave<TScreen: Component + Default>(
+    cmds: &mut Commands,
+    shot: SlowInRadiusShot,
+) {
+    cmds.spawn(microwave_pulse_shape(
+        shot.range_radius * TILE_SIZE,
+        shot.pos_start,
+    ))
+    .insert(shot)
+    .insert(MicrowaveShot)
+    .insert(TScreen::default());
+}
+
+// Ring that grows to the range with every pulse
+fn microwave_pulse_shape(radius: f32, pos: Vec2Board) -> impl Bundle {
+    (
+        ShapeBuilder::with(&shapes::Circle {
+            center: Vec2::default(),
+            radius,
+        })
+        .fill(Srgba {
+            red: 1.,
+            green: 0.55,
+            blue: 0.,
+            alpha: 0.15,
+        })
+        .stroke(Stroke::new(
+            Srgba {
+                red: 1.,
+                green: 0.55,
+                blue: 0.,
+                alpha: 0.6,
+            },
+            TILE_SIZE / 24.,
+        ))
+        .build(),
+        Transform {
+            translation: pos.to_scaled_vec3(0.9),
+            scale: Vec3::new(0., 0., 1.),
+            ..Default::default()
+        },
+    )
+}
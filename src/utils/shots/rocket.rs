@@ -1,13 +1,14 @@
 use super::{DamageInRadiusTargetPosShot, DamageInRadiusTargetPosShotValues, Shot};
 use crate::{
     balance::{
-        ROCKET_DAMAGE, ROCKET_DAMAGE_RADIUS, ROCKET_FUEL_CONSUMPTION_PER_SECOND,
-        ROCKET_FUEL_RANGE_TILES, ROCKET_RANGE_RADIUS, ROCKET_SPEED_TILES_PER_SECOND,
+        ROCKET_ARMOR_PIERCING, ROCKET_DAMAGE, ROCKET_DAMAGE_RADIUS,
+        ROCKET_FUEL_CONSUMPTION_PER_SECOND, ROCKET_FUEL_RANGE_TILES, ROCKET_RANGE_RADIUS,
+        ROCKET_SPEED_TILES_PER_SECOND,
     },
     board::visualisation::TILE_SIZE,
     utils::{
-        buffer::Buffer, materials::MATERIALS_COLOR, resource_bar::spawn_resource_bar, Amount,
-        Materials, Vec2Board,
+        buffer::Buffer, damage::DamageType, interpolation::TickTranslation,
+        materials::MATERIALS_COLOR, resource_bar::spawn_resource_bar, Amount, Materials, Vec2Board,
     },
 };
 use bevy::color::palettes::css::{DIM_GRAY, PURPLE};
@@ -26,6 +27,8 @@ impl Shot {
             pos_start: pos,
             pos,
             damage: ROCKET_DAMAGE,
+            damage_type: DamageType::Explosive,
+            armor_piercing: ROCKET_ARMOR_PIERCING,
             damage_radius: ROCKET_DAMAGE_RADIUS,
             range_radius: INIT_RANGE_RADIUS,
             speed: ROCKET_SPEED_TILES_PER_SECOND,
@@ -60,8 +63,10 @@ pub fn spawn_shot_rocket<TScreen: Component + Default>(
     cmds: &mut Commands,
     shot: DamageInRadiusTargetPosShot,
 ) {
+    let translation = shot.scaled_pos(1.);
     cmds.spawn((
-        Transform::from_translation(Vec3::new(0., 0., 1.)),
+        Transform::from_translation(translation),
+        TickTranslation::new(translation),
         Visibility::Inherited,
         shot,
         RocketShot,
@@ -1,7 +1,8 @@
 use super::TilesPerSecond;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct Speed {
     pub normal: TilesPerSecond,
     pub current: TilesPerSecond,
@@ -0,0 +1,193 @@
+use super::{
+    damage::{Damage, DamageType},
+    IngameTimestamp,
+};
+use crate::board::visualisation::TILE_SIZE;
+use bevy::color::palettes::css::{DEEP_SKY_BLUE, ORANGE_RED, VIOLET, YELLOW};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::{EnumDiscriminants, EnumIter, IntoEnumIterator};
+
+// More burns on one enemy replace the one ending first
+pub const MAX_BURN_STACKS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumDiscriminants)]
+#[strum_discriminants(derive(Component, EnumIter, Hash))]
+#[strum_discriminants(name(StatusEffectType))]
+pub enum StatusEffectKind {
+    // Scales the target speed, the strongest slow wins
+    Slow(f32),
+    // Damage per second, stacks up to MAX_BURN_STACKS
+    Burn(f32, DamageType),
+    // No movement at all
+    Stun,
+    // Multiplies all incoming damage, the strongest one wins
+    Vulnerability(f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub until: IngameTimestamp,
+}
+
+#[derive(Component, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffects(Vec<StatusEffect>);
+
+impl StatusEffects {
+    // Same effects only get extended, so the list stays short
+    pub fn add(&mut self, effect: StatusEffect) {
+        if let StatusEffectKind::Burn(..) = effect.kind {
+            self.0.push(effect);
+            let burns = self.of_type(StatusEffectType::Burn).count();
+            if burns > MAX_BURN_STACKS {
+                if let Some(first_ending) = self.first_ending(StatusEffectType::Burn) {
+                    self.0.remove(first_ending);
+                }
+            }
+            return;
+        }
+        match self.0.iter_mut().find(|other| other.kind == effect.kind) {
+            Some(other) => other.until = IngameTimestamp::new(other.until.max(*effect.until)),
+            None => self.0.push(effect),
+        }
+    }
+
+    pub fn remove_expired(&mut self, now: IngameTimestamp) {
+        self.0.retain(|effect| effect.until > now);
+    }
+
+    pub fn has(&self, effect_type: StatusEffectType) -> bool {
+        self.of_type(effect_type).next().is_some()
+    }
+
+    pub fn speed_factor(&self) -> f32 {
+        if self.has(StatusEffectType::Stun) {
+            return 0.;
+        }
+        self.0
+            .iter()
+            .filter_map(|effect| match effect.kind {
+                StatusEffectKind::Slow(factor) => Some(factor.clamp(0., 1.)),
+                _ => None,
+            })
+            .fold(1., f32::min)
+    }
+
+    pub fn damage_multiplier(&self) -> f32 {
+        self.0
+            .iter()
+            .filter_map(|effect| match effect.kind {
+                StatusEffectKind::Vulnerability(multiplier) => Some(multiplier),
+                _ => None,
+            })
+            .fold(1., f32::max)
+    }
+
+    // Burn damage of one tick, one per damage type
+    pub fn burn_damages(&self, delta_secs: f32) -> Vec<Damage> {
+        DamageType::iter()
+            .filter_map(|damage_type| {
+                let per_second: f32 = self
+                    .0
+                    .iter()
+                    .filter_map(|effect| match effect.kind {
+                        StatusEffectKind::Burn(per_second, kind) if kind == damage_type => {
+                            Some(per_second)
+                        }
+                        _ => None,
+                    })
+                    .sum();
+                (per_second > 0.).then(|| Damage::new(per_second * delta_secs, damage_type))
+            })
+            .collect()
+    }
+
+    fn of_type(&self, effect_type: StatusEffectType) -> impl Iterator<Item = &StatusEffect> {
+        self.0
+            .iter()
+            .filter(move |effect| StatusEffectType::from(effect.kind) == effect_type)
+    }
+
+    fn first_ending(&self, effect_type: StatusEffectType) -> Option<usize> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, effect)| StatusEffectType::from(effect.kind) == effect_type)
+            .min_by(|(_, a), (_, b)| a.until.total_cmp(&b.until))
+            .map(|(i, _)| i)
+    }
+}
+
+// One small dot per effect type over the enemy, shown while the effect lasts
+pub fn status_effect_indicators(parent: &mut ChildSpawnerCommands, enemy_radius_px: f32) {
+    let dot_radius = TILE_SIZE / 32.;
+    for (i, effect_type) in StatusEffectType::iter().enumerate() {
+        let x = (i as f32 - 1.5) * dot_radius * 2.5;
+        parent.spawn((
+            ShapeBuilder::with(&shapes::Circle {
+                center: Vec2::default(),
+                radius: dot_radius,
+            })
+            .fill(indicator_color(effect_type))
+            .build(),
+            Transform::from_translation(Vec3::new(x, enemy_radius_px + dot_radius * 2., 0.2)),
+            Visibility::Hidden,
+            effect_type,
+        ));
+    }
+}
+
+fn indicator_color(effect_type: StatusEffectType) -> Color {
+    match effect_type {
+        StatusEffectType::Slow => DEEP_SKY_BLUE,
+        StatusEffectType::Burn => ORANGE_RED,
+        StatusEffectType::Stun => YELLOW,
+        StatusEffectType::Vulnerability => VIOLET,
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn effect(kind: StatusEffectKind, until: f32) -> StatusEffect {
+        StatusEffect {
+            kind,
+            until: IngameTimestamp::new(until),
+        }
+    }
+
+    #[test]
+    fn test_stacking_rules() {
+        let mut effects = StatusEffects::default();
+        effects.add(effect(StatusEffectKind::Slow(0.5), 2.));
+        effects.add(effect(StatusEffectKind::Slow(0.8), 5.));
+        effects.add(effect(StatusEffectKind::Slow(0.5), 3.));
+        assert_eq!(effects.0.len(), 2);
+        assert_eq!(effects.speed_factor(), 0.5);
+
+        for until in 1..=4 {
+            effects.add(effect(
+                StatusEffectKind::Burn(10., DamageType::Energy),
+                until as f32,
+            ));
+        }
+        assert_eq!(
+            effects.of_type(StatusEffectType::Burn).count(),
+            MAX_BURN_STACKS
+        );
+        assert_eq!(
+            effects.burn_damages(0.5),
+            vec![Damage::new(15., DamageType::Energy)]
+        );
+
+        effects.add(effect(StatusEffectKind::Stun, 1.));
+        assert_eq!(effects.speed_factor(), 0.);
+        effects.remove_expired(IngameTimestamp::new(3.));
+        assert_eq!(effects.speed_factor(), 0.8);
+        assert_eq!(effects.of_type(StatusEffectType::Burn).count(), 1);
+    }
+}
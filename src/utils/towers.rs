@@ -1,17 +1,28 @@
-use self::{laser::spawn_laser_tower, rocket::spawn_rocket_tower};
+use self::{
+    grenade::spawn_grenade_tower, laser::spawn_laser_tower, microwave::spawn_microwave_tower,
+    rocket::spawn_rocket_tower,
+};
 use super::{
     shots::{Shot, TowerStatus},
-    Vec2Board,
+    IngameTimestamp, Vec2Board,
+};
+use crate::{
+    balance::{
+        TOWER_MAX_LEVEL, TOWER_UPGRADE_DAMAGE_FACTOR, TOWER_UPGRADE_RANGE_FACTOR,
+        TOWER_UPGRADE_RELOAD_FACTOR, TOWER_UPGRADE_SHOT_DURATION_FACTOR,
+    },
+    board::visualisation::TILE_SIZE,
 };
-use crate::board::visualisation::TILE_SIZE;
-use bevy::color::palettes::css::{DARK_RED, DIM_GRAY, SILVER};
+use bevy::color::palettes::css::{DIM_GRAY, GOLD, SILVER};
 use bevy::prelude::*;
 use bevy_prototype_lyon::{prelude::*, shapes::Circle};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
-use strum::EnumDiscriminants;
+use strum::{Display, EnumDiscriminants, EnumIter};
 
+mod grenade;
 mod laser;
+mod microwave;
 mod rocket;
 
 //pub struct Tower {
@@ -20,7 +31,7 @@ mod rocket;
 //}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Component, EnumDiscriminants)]
-#[strum_discriminants(derive(Component))]
+#[strum_discriminants(derive(Component, Serialize, Deserialize))]
 #[strum_discriminants(name(Towerless))]
 pub enum Tower {
     // Damages enemies, needs energy
@@ -37,6 +48,15 @@ pub enum Tower {
 }
 
 impl Tower {
+    pub fn new(kind: Towerless, pos: Vec2Board) -> Option<Self> {
+        match kind {
+            Towerless::Laser => Some(Self::laser(pos)),
+            Towerless::Microwave => Some(Self::microwave(pos)),
+            Towerless::Rocket => Some(Self::rocket(pos)),
+            Towerless::Grenade => Some(Self::grenade(pos)),
+        }
+    }
+
     pub fn values(&self) -> &TowerValues {
         match self {
             Tower::Laser(values) => values,
@@ -58,13 +78,28 @@ impl Tower {
     pub fn draw_preview<TScreen: Component + Default>(&self, cmds: &mut Commands) {
         match self {
             Tower::Laser(values) => spawn_laser_tower::<TScreen>(cmds, values.clone(), true),
-            Tower::Microwave(_) => todo!(),
+            Tower::Microwave(values) => {
+                spawn_microwave_tower::<TScreen>(cmds, values.clone(), true)
+            }
             Tower::Rocket(values) => spawn_rocket_tower::<TScreen>(cmds, values.clone(), true),
-            Tower::Grenade(_) => todo!(),
+            Tower::Grenade(values) => spawn_grenade_tower::<TScreen>(cmds, values.clone(), true),
         }
     }
 }
 
+// Which enemy in range a tower picks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Display, EnumIter)]
+pub enum Targeting {
+    // Furthest along the road
+    #[default]
+    First,
+    Last,
+    Strongest,
+    Weakest,
+    Closest,
+    Fastest,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TowerValues {
     pub pos: Vec2Board,
@@ -72,6 +107,11 @@ pub struct TowerValues {
     pub shot: Shot,
     pub reload_duration: Duration,
     pub shoot_duration: Duration,
+    // Flying enemies can only be shot by towers with this set
+    pub can_hit_air: bool,
+    pub targeting: Targeting,
+    pub level: u8,
+    pub built_at: IngameTimestamp,
 
     // temp values
     pub target_lock: Option<Entity>,
@@ -85,9 +125,42 @@ impl TowerValues {
         match &mut new_vals.shot {
             Shot::Laser(shot) => shot.pos_start = pos,
             Shot::Rocket(shot) => shot.pos = pos,
+            Shot::Microwave(shot) => shot.pos_start = pos,
+            Shot::Grenade(shot) => shot.pos_start = pos,
         };
         new_vals
     }
+
+    // Shots check the range on their own, so they scale along
+    pub fn scale_range(&mut self, factor: f32) {
+        self.range_radius *= factor;
+        match &mut self.shot {
+            Shot::Laser(shot) => shot.range_radius *= factor,
+            Shot::Rocket(shot) => shot.range_radius *= factor,
+            Shot::Microwave(shot) => shot.range_radius *= factor,
+            Shot::Grenade(shot) => shot.range_radius *= factor,
+        };
+    }
+
+    pub fn can_upgrade(&self) -> bool {
+        self.level < TOWER_MAX_LEVEL
+    }
+
+    pub fn upgrade(&mut self) {
+        if !self.can_upgrade() {
+            return;
+        }
+        self.level += 1;
+        self.scale_range(TOWER_UPGRADE_RANGE_FACTOR);
+        self.shot.upgrade(
+            TOWER_UPGRADE_DAMAGE_FACTOR,
+            TOWER_UPGRADE_SHOT_DURATION_FACTOR,
+        );
+        self.reload_duration = self.reload_duration.mul_f32(TOWER_UPGRADE_RELOAD_FACTOR);
+        self.shoot_duration = self
+            .shoot_duration
+            .mul_f32(TOWER_UPGRADE_SHOT_DURATION_FACTOR);
+    }
 }
 
 #[derive(Component)]
@@ -99,6 +172,10 @@ pub struct ChildOfTower;
 #[derive(Component, Deref, DerefMut)]
 pub struct TowerRangeCircle(UVec2);
 
+// Shown from the given tower level on
+#[derive(Component)]
+pub struct TowerLevelPip(pub u8);
+
 pub fn draw_tower<TScreen: Component + Default>(
     cmds: &mut Commands,
     pos: Vec2Board,
@@ -106,9 +183,13 @@ pub fn draw_tower<TScreen: Component + Default>(
 ) {
     match tower {
         Tower::Laser(vals) => spawn_laser_tower::<TScreen>(cmds, vals.clone_with_pos(pos), false),
-        Tower::Microwave(_) => todo!(),
+        Tower::Microwave(vals) => {
+            spawn_microwave_tower::<TScreen>(cmds, vals.clone_with_pos(pos), false)
+        }
         Tower::Rocket(vals) => spawn_rocket_tower::<TScreen>(cmds, vals.clone_with_pos(pos), false),
-        Tower::Grenade(_) => todo!(),
+        Tower::Grenade(vals) => {
+            spawn_grenade_tower::<TScreen>(cmds, vals.clone_with_pos(pos), false)
+        }
     };
 }
 
@@ -136,16 +217,68 @@ fn tower_circle_shape() -> impl Bundle {
     )
 }
 
-fn tower_range_circle_shape(radius: f32, visibility: Visibility) -> impl Bundle {
-    (
-        ShapeBuilder::with(&Circle {
-            center: Vec2::default(),
-            radius: radius * TILE_SIZE,
-        })
+// One gold pip per level above the first, below the tower center
+fn tower_level_pips(parent: &mut ChildSpawnerCommands) {
+    let pip_count = TOWER_MAX_LEVEL - 1;
+    for i in 0..pip_count {
+        let x = (i as f32 - (pip_count - 1) as f32 / 2.) * TILE_SIZE / 7.;
+        parent.spawn((
+            ShapeBuilder::with(&Circle {
+                center: Vec2::default(),
+                radius: TILE_SIZE / 18.,
+            })
+            .fill(GOLD)
+            .stroke(Stroke::new(DIM_GRAY, TILE_SIZE / 48.))
+            .build(),
+            Transform::from_xyz(x, -TILE_SIZE / 3.6, 0.5),
+            Visibility::Hidden,
+            TowerLevelPip(i + 2),
+        ));
+    }
+}
+
+fn range_circle(radius: f32) -> Circle {
+    Circle {
+        center: Vec2::default(),
+        radius: radius * TILE_SIZE,
+    }
+}
+
+// Redraws the circle for a new range, scaling its transform would widen the stroke too
+pub fn set_range_circle_radius(shape: &mut Shape, radius: f32) {
+    shape.path = ShapeBuilder::with(&range_circle(radius))
         .fill(Color::NONE)
-        .stroke(Stroke::new(DARK_RED, 0.025 * TILE_SIZE))
-        .build(),
+        .build()
+        .path;
+}
+
+fn tower_range_circle_shape(radius: f32, color: Color, visibility: Visibility) -> impl Bundle {
+    (
+        ShapeBuilder::with(&range_circle(radius))
+            .fill(Color::NONE)
+            .stroke(Stroke::new(color, 0.025 * TILE_SIZE))
+            .build(),
         visibility,
         Transform::from_xyz(0., 0., 0.3),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upgrade_stops_at_max_level() {
+        let mut vals = TowerValues::laser(Vec2Board::new(1., 1.));
+        let (range, damage) = (vals.range_radius, vals.shot.damage());
+        for _ in 0..TOWER_MAX_LEVEL + 2 {
+            vals.upgrade();
+        }
+        assert_eq!(vals.level, TOWER_MAX_LEVEL);
+        assert!(!vals.can_upgrade());
+        let upgrades = (TOWER_MAX_LEVEL - 1) as i32;
+        let expected_range = range * TOWER_UPGRADE_RANGE_FACTOR.powi(upgrades);
+        assert!((vals.range_radius - expected_range).abs() < 0.001);
+        assert!(vals.shot.damage() > damage);
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use super::{
+    tower_base_shape, tower_circle_shape, tower_level_pips, tower_range_circle_shape, ChildOfTower,
+    Targeting, Tower, TowerCannon, TowerRangeCircle, TowerValues,
+};
+use crate::{
+    balance::{GRENADE_RELOAD_DURATION_SECS, GRENADE_SHOOT_DURATION_SECS},
+    board::visualisation::TILE_SIZE,
+    utils::{
+        shots::{Shot, TowerStatus},
+        visible, BoardPos, IngameTimestamp, Vec2Board,
+    },
+};
+use bevy::color::palettes::css::{DARK_RED, DIM_GRAY, OLIVE, SILVER};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+impl Tower {
+    pub fn grenade(pos: Vec2Board) -> Self {
+        Self::Grenade(TowerValues::grenade(pos))
+    }
+}
+
+impl TowerValues {
+    pub fn grenade(pos: Vec2Board) -> Self {
+        use super::super::shots::grenade;
+        Self {
+            pos,
+            range_radius: grenade::INIT_RANGE_RADIUS,
+            shot: Shot::grenade(pos),
+            reload_duration: Duration::from_secs_f32(GRENADE_RELOAD_DURATION_SECS),
+            shoot_duration: Duration::from_secs_f32(GRENADE_SHOOT_DURATION_SECS),
+            // Lands on the ground, flying enemies are out of reach
+            can_hit_air: false,
+            targeting: Targeting::default(),
+            level: 1,
+            built_at: IngameTimestamp::default(),
+
+            target_lock: None,
+            tower_status: TowerStatus::Waiting,
+        }
+    }
+}
+
+pub(super) fn spawn_grenade_tower<TScreen: Component + Default>(
+    cmds: &mut Commands,
+    vals: TowerValues,
+    is_preview: bool,
+) {
+    let mut color = OLIVE;
+    if is_preview {
+        color.alpha = 0.9;
+    }
+    let transform = Transform::from_translation(vals.pos.to_scaled_vec3(1.));
+    cmds.spawn((
+        transform,
+        Visibility::Inherited,
+        ChildOfTower,
+        BoardPos(vals.pos.as_uvec2()),
+        TScreen::default(),
+    ))
+    .with_children(|parent| {
+        grenade_tower_children::<TScreen>(parent, &vals, color.into(), is_preview);
+    })
+    .insert(Tower::Grenade(vals));
+}
+
+fn grenade_tower_children<TScreen: Component + Default>(
+    parent: &mut ChildSpawnerCommands,
+    vals: &TowerValues,
+    color: Color,
+    is_preview: bool,
+) {
+    // Tower base
+    parent.spawn(tower_base_shape(color));
+
+    // Level pips
+    tower_level_pips(parent);
+
+    // Tower circle
+    parent.spawn(tower_circle_shape());
+
+    // Tower cannon
+    parent.spawn(tower_grenade_cannon()).insert(TowerCannon);
+
+    // Range circle
+    let range_radius = match is_preview {
+        true => vals.range_radius * 2.,
+        false => vals.range_radius,
+    };
+    let range_circle = tower_range_circle_shape(range_radius, DARK_RED.into(), visible(is_preview));
+    parent
+        .spawn(range_circle)
+        .insert(TowerRangeCircle(vals.pos.as_uvec2()))
+        .insert(TScreen::default());
+}
+
+// Short and wide mortar barrel
+fn tower_grenade_cannon() -> impl Bundle {
+    (
+        ShapeBuilder::with(&shapes::Rectangle {
+            origin: RectangleOrigin::CustomCenter(Vec2::new(0., TILE_SIZE / 6.)),
+            extents: Vec2::new(TILE_SIZE / 4., TILE_SIZE / 3.),
+            radii: Some(BorderRadii::single(TILE_SIZE / 16.)),
+        })
+        .fill(SILVER)
+        .stroke(Stroke::new(DIM_GRAY, TILE_SIZE / 16.))
+        .build(),
+        Transform {
+            translation: Vec3::new(0., 0., 0.3),
+            rotation: Quat::from_rotation_z(0.),
+            ..Default::default()
+        },
+    )
+}
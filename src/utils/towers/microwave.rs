@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use super::{
+    tower_base_shape, tower_level_pips, tower_range_circle_shape, ChildOfTower, Targeting, Tower,
+    TowerCannon, TowerRangeCircle, TowerValues,
+};
+use crate::{
+    balance::MICROWAVE_RELOAD_DURATION_SECS,
+    board::visualisation::TILE_SIZE,
+    utils::{
+        shots::{Shot, TowerStatus},
+        visible, BoardPos, IngameTimestamp, Vec2Board,
+    },
+};
+use bevy::color::palettes::css::{DARK_ORANGE, DIM_GRAY, SILVER};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+impl Tower {
+    pub fn microwave(pos: Vec2Board) -> Self {
+        Self::Microwave(TowerValues::microwave(pos))
+    }
+}
+
+impl TowerValues {
+    pub fn microwave(pos: Vec2Board) -> Self {
+        use super::super::shots::microwave;
+        Self {
+            pos,
+            range_radius: microwave::INIT_RANGE_RADIUS,
+            shot: Shot::microwave(pos),
+            reload_duration: Duration::from_secs_f32(MICROWAVE_RELOAD_DURATION_SECS),
+            shoot_duration: Duration::from_secs_f32(microwave::INIT_SHOT_DURATION_SECS),
+            can_hit_air: true,
+            targeting: Targeting::default(),
+            level: 1,
+            built_at: IngameTimestamp::default(),
+
+            target_lock: None,
+            tower_status: TowerStatus::Waiting,
+        }
+    }
+}
+
+pub(super) fn spawn_microwave_tower<TScreen: Component + Default>(
+    cmds: &mut Commands,
+    vals: TowerValues,
+    is_preview: bool,
+) {
+    let mut color = DARK_ORANGE;
+    if is_preview {
+        color.alpha = 0.9;
+    }
+    let transform = Transform::from_translation(vals.pos.to_scaled_vec3(1.));
+    cmds.spawn((
+        transform,
+        Visibility::Inherited,
+        ChildOfTower,
+        BoardPos(vals.pos.as_uvec2()),
+        TScreen::default(),
+    ))
+    .with_children(|parent| {
+        microwave_tower_children::<TScreen>(parent, &vals, color.into(), is_preview)
+    })
+    .insert(Tower::Microwave(vals));
+}
+
+fn microwave_tower_children<TScreen: Component + Default>(
+    parent: &mut ChildSpawnerCommands,
+    vals: &TowerValues,
+    color: Color,
+    is_preview: bool,
+) {
+    // Tower base
+    parent.spawn(tower_base_shape(color));
+
+    // Level pips
+    tower_level_pips(parent);
+
+    // Emitter rings
+    parent.spawn(microwave_ring_shape(TILE_SIZE / 3.2, 0.2));
+    parent.spawn(microwave_ring_shape(TILE_SIZE / 4.6, 0.3));
+
+    // Emitter, glows like the other cannons but never turns
+    parent.spawn(microwave_emitter_shape()).insert(TowerCannon);
+
+    // Range circle
+    let range_radius = match is_preview {
+        true => vals.range_radius * 2.,
+        false => vals.range_radius,
+    };
+    let range_circle =
+        tower_range_circle_shape(range_radius, DARK_ORANGE.into(), visible(is_preview));
+    parent
+        .spawn(range_circle)
+        .insert(TowerRangeCircle(vals.pos.as_uvec2()))
+        .insert(TScreen::default());
+}
+
+fn microwave_ring_shape(radius: f32, z: f32) -> impl Bundle {
+    (
+        ShapeBuilder::with(&shapes::Circle {
+            center: Vec2::default(),
+            radius,
+        })
+        .stroke(Stroke::new(SILVER, TILE_SIZE / 24.))
+        .build(),
+        Transform::from_xyz(0., 0., z),
+    )
+}
+
+fn microwave_emitter_shape() -> impl Bundle {
+    (
+        ShapeBuilder::with(&shapes::Circle {
+            center: Vec2::default(),
+            radius: TILE_SIZE / 9.,
+        })
+        .fill(SILVER)
+        .stroke(Stroke::new(DIM_GRAY, TILE_SIZE / 24.))
+        .build(),
+        Transform::from_xyz(0., 0., 0.4),
+    )
+}
@@ -1,18 +1,18 @@
 use std::time::Duration;
 
 use super::{
-    tower_base_shape, tower_circle_shape, tower_range_circle_shape, ChildOfTower, Tower,
-    TowerCannon, TowerRangeCircle, TowerValues,
+    tower_base_shape, tower_circle_shape, tower_level_pips, tower_range_circle_shape, ChildOfTower,
+    Targeting, Tower, TowerCannon, TowerRangeCircle, TowerValues,
 };
 use crate::{
     balance::{ROCKET_RELOAD_DURATION_SECS, ROCKET_SHOOT_DURATION_SECS},
     board::visualisation::TILE_SIZE,
     utils::{
         shots::{Shot, TowerStatus},
-        visible, BoardPos, Vec2Board,
+        visible, BoardPos, IngameTimestamp, Vec2Board,
     },
 };
-use bevy::color::palettes::css::{DIM_GRAY, PURPLE, SILVER};
+use bevy::color::palettes::css::{DARK_RED, DIM_GRAY, PURPLE, SILVER};
 use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
 
@@ -31,6 +31,11 @@ impl TowerValues {
             shot: Shot::rocket(pos),
             reload_duration: Duration::from_secs_f32(ROCKET_RELOAD_DURATION_SECS),
             shoot_duration: Duration::from_secs_f32(ROCKET_SHOOT_DURATION_SECS),
+            // Explosions miss flying enemies
+            can_hit_air: false,
+            targeting: Targeting::default(),
+            level: 1,
+            built_at: IngameTimestamp::default(),
 
             target_lock: None,
             tower_status: TowerStatus::Waiting,
@@ -70,6 +75,9 @@ fn rocket_tower_children<TScreen: Component + Default>(
     // Tower base
     parent.spawn(tower_base_shape(color));
 
+    // Level pips
+    tower_level_pips(parent);
+
     // Tower circle
     parent.spawn(tower_circle_shape());
 
@@ -81,7 +89,7 @@ fn rocket_tower_children<TScreen: Component + Default>(
         true => vals.range_radius * 2.,
         false => vals.range_radius,
     };
-    let range_circle = tower_range_circle_shape(range_radius, visible(is_preview));
+    let range_circle = tower_range_circle_shape(range_radius, DARK_RED.into(), visible(is_preview));
     parent
         .spawn(range_circle)
         .insert(TowerRangeCircle(vals.pos.as_uvec2()))